@@ -4,12 +4,15 @@ use std::sync::Arc;
 use codec::Codec;
 use jsonrpc_core::{Error, ErrorCode, Result};
 use jsonrpc_derive::rpc;
+use serde::Serialize;
 
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 
-use ci_primitives::ArtistId;
-use cirml_artvenuses_runtime_api::ArtvenusesApi as ArtvenusesRuntimeApi;
+use ci_primitives::{ArtistId, SellCurrency};
+use cirml_artvenuses_runtime_api::{
+    AcquisitionType, ArtvenusesApi as ArtvenusesRuntimeApi, ProvenanceRecord,
+};
 
 pub struct Artvenuses<C, B> {
     client: Arc<C>,
@@ -27,7 +30,7 @@ impl<C, B> Artvenuses<C, B> {
 }
 
 #[rpc]
-pub trait ArtvenusesApi<BlockHash, AccountId, ArtvenusId> {
+pub trait ArtvenusesApi<BlockHash, AccountId, ArtvenusId, BlockNumber, Balance> {
     #[rpc(name = "artvenuses_getArtvenuses")]
     fn artvenuses(&self, at: Option<BlockHash>) -> Result<Vec<ArtvenusId>>;
 
@@ -44,18 +47,29 @@ pub trait ArtvenusesApi<BlockHash, AccountId, ArtvenusId> {
         account_id: AccountId,
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<u64, ArtvenusId>>;
+
+    /// The holders of the artvenus in turn, the earliest first.
+    #[rpc(name = "artvenuses_getProvenance")]
+    fn provenance(
+        &self,
+        id: ArtvenusId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<ProvenanceRecordForRpc<AccountId, BlockNumber>>>;
 }
 
-impl<C, Block, AccountId, ArtvenusId> ArtvenusesApi<<Block as BlockT>::Hash, AccountId, ArtvenusId>
+impl<C, Block, AccountId, ArtvenusId, BlockNumber, Balance>
+    ArtvenusesApi<<Block as BlockT>::Hash, AccountId, ArtvenusId, BlockNumber, Balance>
     for Artvenuses<C, Block>
 where
     C: sp_api::ProvideRuntimeApi<Block>,
     C: HeaderBackend<Block>,
     C: Send + Sync + 'static,
-    C::Api: ArtvenusesRuntimeApi<Block, AccountId, ArtvenusId>,
+    C::Api: ArtvenusesRuntimeApi<Block, AccountId, ArtvenusId, BlockNumber, Balance>,
     Block: BlockT,
-    AccountId: Clone + std::fmt::Display + Codec,
+    AccountId: Clone + std::fmt::Display + Codec + Serialize,
     ArtvenusId: Clone + std::fmt::Display + Codec,
+    BlockNumber: Clone + std::fmt::Display + Codec + Serialize,
+    Balance: Clone + std::fmt::Display + Codec + ToString,
 {
     fn artvenuses(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<ArtvenusId>> {
         let api = self.client.runtime_api();
@@ -86,6 +100,43 @@ where
             .map(|list| list.into_iter().collect())
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn provenance(
+        &self,
+        id: ArtvenusId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<ProvenanceRecordForRpc<AccountId, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.provenance(&at, id)
+            .map(|list| list.into_iter().map(Into::into).collect())
+            .map_err(runtime_error_into_rpc_err)
+    }
+}
+
+#[derive(Serialize)]
+pub struct ProvenanceRecordForRpc<AccountId, BlockNumber> {
+    holder: AccountId,
+    block: BlockNumber,
+    acquisition: AcquisitionType,
+    price: Option<String>,
+    currency: SellCurrency,
+}
+
+impl<AccountId, BlockNumber, Balance> From<ProvenanceRecord<AccountId, BlockNumber, Balance>>
+    for ProvenanceRecordForRpc<AccountId, BlockNumber>
+where
+    Balance: ToString,
+{
+    fn from(record: ProvenanceRecord<AccountId, BlockNumber, Balance>) -> Self {
+        ProvenanceRecordForRpc {
+            holder: record.holder,
+            block: record.block,
+            acquisition: record.acquisition,
+            price: record.price.map(|price| price.to_string()),
+            currency: record.currency,
+        }
+    }
 }
 
 // TODO remove in future
@@ -5,16 +5,21 @@ use codec::Codec;
 use sp_std::prelude::Vec;
 
 use ci_primitives::ArtistId;
+pub use cirml_artvenuses::{AcquisitionType, ProvenanceRecord};
 
 sp_api::decl_runtime_apis! {
-    pub trait ArtvenusesApi<AccountId, ArtvenusId> where
+    pub trait ArtvenusesApi<AccountId, ArtvenusId, BlockNumber, Balance> where
         AccountId: Codec,
         ArtvenusId: Codec,
+        BlockNumber: Codec,
+        Balance: Codec,
     {
         fn artvenuses() -> Vec<ArtvenusId>;
 
         fn artvenuses_of_artist(artist_id: ArtistId) -> Vec<(u64, ArtvenusId)>;
 
         fn artvenuses_of_holder(account_id: AccountId) -> Vec<(u64, ArtvenusId)>;
+
+        fn provenance(id: ArtvenusId) -> Vec<ProvenanceRecord<AccountId, BlockNumber, Balance>>;
     }
 }
@@ -1,26 +1,35 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
 use codec::{Decode, Encode};
 
 use sp_runtime::{
     traits::{
-        CheckEqual, MaybeDisplay, MaybeMallocSizeOf, MaybeSerializeDeserialize, Member,
-        SimpleBitOps,
+        CheckEqual, Hash, MaybeDisplay, MaybeMallocSizeOf, MaybeSerializeDeserialize, Member,
+        SimpleBitOps, StaticLookup,
     },
     DispatchError, DispatchResult, RuntimeDebug,
 };
 use sp_std::{fmt::Debug, prelude::*};
 
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, IterableStorageDoubleMap,
+    decl_error, decl_event, decl_module, decl_storage, weights::Weight, IterableStorageDoubleMap,
     IterableStorageMap, Parameter,
 };
 use frame_system::{self as system, ensure_signed};
 
-use ci_primitives::{ArtistId, Text};
+use ci_primitives::{ArtistId, SellCurrency, Text};
+
+mod migration;
+mod mock;
+mod tests;
 
 pub type ArtvenusId<T> = <T as Trait>::Hash;
-pub type Artvenus<T> = ArtvenusInfo<<T as frame_system::Trait>::BlockNumber>;
+/// The digest of the content of an artvenus.
+pub type ContentHash<T> = <T as frame_system::Trait>::Hash;
+pub type Artvenus<T> = ArtvenusInfo<<T as frame_system::Trait>::BlockNumber, ContentHash<T>>;
 
 pub trait Trait: frame_system::Trait + cirml_balances::Trait + cirml_artists::Trait {
     /// Art hash
@@ -37,9 +46,40 @@ pub trait Trait: frame_system::Trait + cirml_balances::Trait + cirml_artists::Tr
         + sp_std::hash::Hash
         + AsRef<[u8]>
         + AsMut<[u8]>
-        + MaybeMallocSizeOf;
+        + MaybeMallocSizeOf
+        + From<ContentHash<Self>>;
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+    /// The market the artvenuses are listed in.
+    type Market: ArtvenusMarket<ArtvenusId<Self>>;
+}
+
+/// The market of the artvenuses, a listed artvenus could not be transferred by the holder.
+pub trait ArtvenusMarket<ArtvenusId> {
+    fn is_listed(id: ArtvenusId) -> bool;
+    /// The artvenus has been sold once, it could not be transferred and only the artist could
+    /// burn it before.
+    fn is_sold(id: ArtvenusId) -> bool;
+    /// Called after the artvenus is transferred out of the market, e.g. to drop the terms made
+    /// by the former holder.
+    fn on_transfer(id: ArtvenusId);
+    /// Called before the artvenus is burned, everything of it in the market must be dropped.
+    fn on_burn(id: ArtvenusId);
+}
+
+impl<ArtvenusId> ArtvenusMarket<ArtvenusId> for () {
+    fn is_listed(_: ArtvenusId) -> bool {
+        false
+    }
+
+    /// There is no first sale without a market.
+    fn is_sold(_: ArtvenusId) -> bool {
+        true
+    }
+
+    fn on_transfer(_: ArtvenusId) {}
+
+    fn on_burn(_: ArtvenusId) {}
 }
 
 decl_event!(
@@ -49,6 +89,14 @@ decl_event!(
 	{
 	    Create(ArtistId, ArtvenusId),
 	    Move(ArtvenusId, AccountId, AccountId),
+	    /// An artvenus is burned (artvenus, caller).
+	    Burn(ArtvenusId, AccountId),
+	    /// A squatted artvenus is replaced by the one derived from the content (artvenus, squatting artist).
+	    Reclaim(ArtvenusId, ArtistId),
+	    /// The approved account of an artvenus is set or cleared (holder, artvenus, approved).
+	    Approval(AccountId, ArtvenusId, Option<AccountId>),
+	    /// An operator is approved or disapproved for all artvenuses of a holder (holder, operator, approved).
+	    ApprovalForAll(AccountId, AccountId, bool),
 	}
 );
 
@@ -60,15 +108,74 @@ decl_error! {
         ArtvenusAlreadyExist,
         ///
         HolderNotExist,
+        ///
+        NotHolder,
+        ///
+        ArtvenusListed,
+        ///
+        NotApproved,
+        ///
+        NotArtist,
+        ///
+        ArtvenusBurned,
+        /// The artvenus is not sold yet, the artist could only sell it in the market.
+        ArtvenusNotSold,
     }
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
-pub struct ArtvenusInfo<BlockNumber> {
+pub struct ArtvenusInfo<BlockNumber, Hash> {
     pub origin: ArtistId,
     pub time: BlockNumber,
     pub name: Text,
     pub desc: Text,
+    /// the id is derived from the content hash and the metadata if it's set
+    pub content_hash: Option<Hash>,
+}
+
+/// How a holder acquires an artvenus.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum AcquisitionType {
+    Created,
+    /// dealt in the market to the payer
+    Sold,
+    /// moved by the holder or an approved account
+    Transferred,
+    /// dealt in the market to another account than the payer
+    Gifted,
+    /// held before the provenance is recorded, how it's acquired is unknown
+    Migrated,
+}
+
+/// An entry in the provenance of an artvenus.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ProvenanceRecord<AccountId, BlockNumber, Balance> {
+    pub holder: AccountId,
+    pub block: BlockNumber,
+    pub acquisition: AcquisitionType,
+    /// the price of a deal
+    pub price: Option<Balance>,
+    /// the currency of `price`, `Native` without a price
+    pub currency: SellCurrency,
+}
+
+// A value placed in storage that represents the current version of the Artvenuses storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+enum Releases {
+    V1_0_0,
+    V2_0_0,
+    V3_0_0,
+    V4_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
 }
 
 decl_storage! {
@@ -78,11 +185,33 @@ decl_storage! {
         pub ArtistArtvenuses get(fn artist_artvenuses):
             double_map hasher(twox_64_concat) ArtistId, hasher(twox_64_concat) u64 => Option<ArtvenusId<T>>;
         pub ArtistArtvenusNumbers get(fn artist_artvenus_numbers): map hasher(twox_64_concat) ArtistId => u64;
+        /// The number of an artvenus in `ArtistArtvenuses` of its artist.
+        pub ArtistNumberOf get(fn artist_number_of): map hasher(identity) ArtvenusId<T> => Option<u64>;
 
         pub HolderOf get(fn holder_of): map hasher(identity) ArtvenusId<T> => Option<(T::AccountId, u64)>;
         pub HolderArtvenuses get(fn holder_artvenuses):
             double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) u64 => Option<ArtvenusId<T>>;
         pub HolderArtvenusNumbers get(fn holder_artvenus_numbers): map hasher(blake2_128_concat) T::AccountId => u64;
+        /// The block the current holder acquires the artvenus, it's none for the artvenuses
+        /// moved before it's recorded.
+        pub AcquiredAt get(fn acquired_at): map hasher(identity) ArtvenusId<T> => Option<T::BlockNumber>;
+
+        /// The account approved to transfer an artvenus, it's cleared when the holder changes.
+        pub Approvals get(fn approvals): map hasher(identity) ArtvenusId<T> => Option<T::AccountId>;
+        /// The operators approved to transfer all artvenuses of a holder.
+        pub Operators get(fn operators):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => Option<()>;
+
+        /// The holders of an artvenus in turn, it's appended on every move and never changed.
+        pub Provenances get(fn provenances):
+            double_map hasher(identity) ArtvenusId<T>, hasher(twox_64_concat) u64 => Option<ProvenanceRecord<T::AccountId, T::BlockNumber, T::Balance>>;
+        pub ProvenanceNumbers get(fn provenance_numbers): map hasher(identity) ArtvenusId<T> => u64;
+
+        /// The tombstones of the burned artvenuses, the ids could never be created again.
+        pub Burned get(fn burned): map hasher(identity) ArtvenusId<T> => Option<()>;
+
+        /// Storage version of the module.
+        StorageVersion build(|_: &GenesisConfig| Releases::V4_0_0): Releases;
     }
 }
 
@@ -90,11 +219,90 @@ decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            migration::on_runtime_upgrade::<T>()
+        }
+
+        /// Create an artvenus with the caller chosen `id`.
+        ///
+        /// The `id` may be one derived from the content of another artist, such a squatting
+        /// artvenus is dropped when the artist of the content creates it by
+        /// `create_artvenus_from_content`.
         #[weight=0]
         pub fn create_artvenus(origin, id: ArtvenusId<T>, name: Text, desc: Text) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
-            Self::create_artvenus_impl(who, id, name, desc)?;
+            Self::create_artvenus_impl(who, id, name, desc, None)?;
+            Ok(())
+        }
+
+        /// Create an artvenus of `content_hash`, the id is derived by `derive_artvenus_id`, thus
+        /// it could be verified by anyone holds the content. It replaces the artvenus another
+        /// artist created with the id by `create_artvenus`, which is squatting the id.
+        #[weight=0]
+        pub fn create_artvenus_from_content(origin, content_hash: ContentHash<T>, name: Text, desc: Text) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let artist_id = cirml_artists::Module::<T>::get_artist_id(&who)?;
+            let id = Self::derive_artvenus_id(artist_id, &content_hash, &name, &desc);
+
+            Self::create_artvenus_impl(who, id, name, desc, Some(content_hash))?;
+            Ok(())
+        }
+
+        /// Transfer the artvenus `id` of the caller to `dest`, it must be sold once in the market
+        /// and not be listed.
+        #[weight=0]
+        pub fn transfer(origin, id: ArtvenusId<T>, dest: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+
+            Self::transfer_impl(who, id, dest)?;
+            Ok(())
+        }
+
+        /// Burn the artvenus `id`, by the artist before the first sale or by the holder after.
+        #[weight=0]
+        pub fn burn(origin, id: ArtvenusId<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Self::burn_impl(who, id)?;
+            Ok(())
+        }
+
+        /// Approve `approved` to transfer the artvenus `id`, or clear the approval (none), by the
+        /// holder or an operator of the holder.
+        #[weight=0]
+        pub fn approve(origin, id: ArtvenusId<T>, approved: Option<<T::Lookup as StaticLookup>::Source>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let approved = approved.map(T::Lookup::lookup).transpose()?;
+
+            Self::approve_impl(who, id, approved)?;
+            Ok(())
+        }
+
+        /// Approve (true) or disapprove (false) `operator` to transfer all artvenuses of the caller.
+        #[weight=0]
+        pub fn set_approval_for_all(origin, operator: <T::Lookup as StaticLookup>::Source, approved: bool) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let operator = T::Lookup::lookup(operator)?;
+
+            if approved {
+                Operators::<T>::insert(&who, &operator, ());
+            } else {
+                Operators::<T>::remove(&who, &operator);
+            }
+            Self::deposit_event(RawEvent::ApprovalForAll(who, operator, approved));
+            Ok(())
+        }
+
+        /// Transfer the artvenus `id` to `dest`, by the holder, the approved account or an
+        /// operator of the holder.
+        #[weight=0]
+        pub fn transfer_from(origin, id: ArtvenusId<T>, dest: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+
+            Self::transfer_from_impl(who, id, dest)?;
             Ok(())
         }
     }
@@ -137,38 +345,172 @@ impl<T: Trait> Module<T> {
 }
 
 impl<T: Trait> Module<T> {
+    /// The id of the artvenus of `content_hash` with the metadata created by `artist_id`.
+    pub fn derive_artvenus_id(
+        artist_id: ArtistId,
+        content_hash: &ContentHash<T>,
+        name: &Text,
+        desc: &Text,
+    ) -> ArtvenusId<T> {
+        T::Hashing::hash_of(&(artist_id, content_hash, name, desc)).into()
+    }
+
     pub fn create_artvenus_impl(
         who: T::AccountId,
         id: ArtvenusId<T>,
         name: Text,
         desc: Text,
+        content_hash: Option<ContentHash<T>>,
     ) -> DispatchResult {
         let artist_id = cirml_artists::Module::<T>::get_artist_id(&who)?;
-        if Self::get_artvenus(id).is_ok() {
-            Err(Error::<T>::ArtvenusAlreadyExist)?;
+        if Burned::<T>::contains_key(&id) {
+            Err(Error::<T>::ArtvenusBurned)?;
+        }
+        let derived = content_hash.as_ref().map_or(false, |hash| {
+            Self::derive_artvenus_id(artist_id, hash, &name, &desc) == id
+        });
+        if let Ok(existing) = Self::get_artvenus(id) {
+            // only the id derived by the artist is reclaimed from another artist who chose it
+            if !derived || existing.content_hash.is_some() || existing.origin == artist_id {
+                Err(Error::<T>::ArtvenusAlreadyExist)?;
+            }
+            let (holder, holder_number) = Self::holder_info_for(id)?;
+            Self::remove_artvenus(id, existing.origin, &holder, holder_number);
+            Provenances::<T>::remove_prefix(&id);
+            ProvenanceNumbers::<T>::remove(&id);
+            Self::deposit_event(RawEvent::Reclaim(id, existing.origin));
         }
         let info = ArtvenusInfo {
             origin: artist_id,
             time: system::Module::<T>::block_number(),
             name,
             desc,
+            content_hash,
         };
         let number_for_artist = Self::artist_artvenus_numbers(artist_id);
         // artvenus origin
         ArtvenusInfos::<T>::insert(id, info);
         ArtistArtvenuses::<T>::insert(artist_id, number_for_artist, id);
+        ArtistNumberOf::<T>::insert(&id, number_for_artist);
         ArtistArtvenusNumbers::insert(artist_id, number_for_artist + 1);
         // artvenus relationship init
         let number_for_holder = Self::holder_artvenus_numbers(&who);
         HolderOf::<T>::insert(&id, (who.clone(), number_for_holder));
         HolderArtvenuses::<T>::insert(&who, number_for_holder, id);
         HolderArtvenusNumbers::<T>::insert(who, number_for_holder + 1);
+        AcquiredAt::<T>::insert(&id, system::Module::<T>::block_number());
+        Self::record_provenance(id, who, AcquisitionType::Created, None);
 
         Self::deposit_event(RawEvent::Create(artist_id, id));
         Ok(())
     }
 
-    pub fn move_artvenus(id: ArtvenusId<T>, to: &T::AccountId) -> DispatchResult {
+    pub fn transfer_impl(
+        who: T::AccountId,
+        id: ArtvenusId<T>,
+        dest: T::AccountId,
+    ) -> DispatchResult {
+        if !Self::is_holder(id, &who)? {
+            Err(Error::<T>::NotHolder)?;
+        }
+        Self::move_unlisted(id, &dest)
+    }
+
+    pub fn transfer_from_impl(
+        who: T::AccountId,
+        id: ArtvenusId<T>,
+        dest: T::AccountId,
+    ) -> DispatchResult {
+        let holder = Self::holder_for(id)?;
+        if holder != who && !Self::is_approved(&holder, id, &who) {
+            Err(Error::<T>::NotApproved)?;
+        }
+        Self::move_unlisted(id, &dest)
+    }
+
+    pub fn approve_impl(
+        who: T::AccountId,
+        id: ArtvenusId<T>,
+        approved: Option<T::AccountId>,
+    ) -> DispatchResult {
+        let holder = Self::holder_for(id)?;
+        if holder != who && Self::operators(&holder, &who).is_none() {
+            Err(Error::<T>::NotApproved)?;
+        }
+
+        Approvals::<T>::mutate_exists(&id, |a| *a = approved.clone());
+        Self::deposit_event(RawEvent::Approval(holder, id, approved));
+        Ok(())
+    }
+
+    /// `who` is the approved account of `id` or an operator of `holder`.
+    pub fn is_approved(holder: &T::AccountId, id: ArtvenusId<T>, who: &T::AccountId) -> bool {
+        Self::approvals(&id).as_ref() == Some(who) || Self::operators(holder, who).is_some()
+    }
+
+    fn move_unlisted(id: ArtvenusId<T>, dest: &T::AccountId) -> DispatchResult {
+        if T::Market::is_listed(id) {
+            Err(Error::<T>::ArtvenusListed)?;
+        }
+        // the first sale is settled in the market with the virgin sell fees
+        if !T::Market::is_sold(id) {
+            Err(Error::<T>::ArtvenusNotSold)?;
+        }
+
+        Self::move_artvenus(id, dest, AcquisitionType::Transferred, None)?;
+        T::Market::on_transfer(id);
+        Ok(())
+    }
+
+    pub fn burn_impl(who: T::AccountId, id: ArtvenusId<T>) -> DispatchResult {
+        let artvenus = Self::get_artvenus(id)?;
+        let (holder, holder_number) = Self::holder_info_for(id)?;
+        if T::Market::is_sold(id) {
+            if holder != who {
+                Err(Error::<T>::NotHolder)?;
+            }
+        } else {
+            // artist accountid may be changed, thus must get every time
+            let artist_account = cirml_artists::Module::<T>::get_artist_account(artvenus.origin)?;
+            if artist_account != who {
+                Err(Error::<T>::NotArtist)?;
+            }
+        }
+
+        Self::remove_artvenus(id, artvenus.origin, &holder, holder_number);
+        Burned::<T>::insert(&id, ());
+
+        Self::deposit_event(RawEvent::Burn(id, who));
+        Ok(())
+    }
+
+    /// Remove the artvenus `id` of `artist_id` held by `holder`, with everything of it in the
+    /// market, the provenance is kept.
+    fn remove_artvenus(
+        id: ArtvenusId<T>,
+        artist_id: ArtistId,
+        holder: &T::AccountId,
+        holder_number: u64,
+    ) {
+        T::Market::on_burn(id);
+        if let Some(number) = ArtistNumberOf::<T>::take(&id) {
+            ArtistArtvenuses::<T>::remove(artist_id, number);
+        }
+        HolderArtvenuses::<T>::remove(holder, holder_number);
+        HolderOf::<T>::remove(&id);
+        AcquiredAt::<T>::remove(&id);
+        Approvals::<T>::remove(&id);
+        ArtvenusInfos::<T>::remove(&id);
+    }
+
+    /// Move the artvenus `id` to `to`, the move is recorded in the provenance as `acquisition`
+    /// at `price` in its currency.
+    pub fn move_artvenus(
+        id: ArtvenusId<T>,
+        to: &T::AccountId,
+        acquisition: AcquisitionType,
+        price: Option<(T::Balance, SellCurrency)>,
+    ) -> DispatchResult {
         let _ = Self::get_artvenus(id)?;
         let (source, source_number) = Self::holder_info_for(id)?;
         if source == *to {
@@ -186,14 +528,49 @@ impl<T: Trait> Module<T> {
         HolderOf::<T>::insert(&id, (to.clone(), current_number_for_to));
         // update index number for to
         HolderArtvenusNumbers::<T>::insert(to, current_number_for_to + 1);
+        AcquiredAt::<T>::insert(&id, system::Module::<T>::block_number());
+        // the approval is made by the former holder
+        Approvals::<T>::remove(&id);
+        Self::record_provenance(id, to.clone(), acquisition, price);
 
         Self::deposit_event(RawEvent::Move(id, source, to.clone()));
         Ok(())
     }
 }
 
+// provenance
+impl<T: Trait> Module<T> {
+    fn record_provenance(
+        id: ArtvenusId<T>,
+        holder: T::AccountId,
+        acquisition: AcquisitionType,
+        price: Option<(T::Balance, SellCurrency)>,
+    ) {
+        let number = Self::provenance_numbers(&id);
+        let record = ProvenanceRecord {
+            holder,
+            block: system::Module::<T>::block_number(),
+            acquisition,
+            price: price.map(|(price, _)| price),
+            currency: price.map_or_else(Default::default, |(_, currency)| currency),
+        };
+        Provenances::<T>::insert(&id, number, record);
+        ProvenanceNumbers::<T>::insert(&id, number + 1);
+    }
+}
+
 // for runtime-api
 impl<T: Trait> Module<T> {
+    /// The provenance of the artvenus `id`, the earliest first. It begins with a `Migrated`
+    /// record for the artvenuses created before the provenance is recorded.
+    pub fn provenance(
+        id: ArtvenusId<T>,
+    ) -> Vec<ProvenanceRecord<T::AccountId, T::BlockNumber, T::Balance>> {
+        (0..Self::provenance_numbers(&id))
+            .filter_map(|number| Self::provenances(&id, number))
+            .collect()
+    }
+
     pub fn artvenuses() -> Vec<ArtvenusId<T>> {
         ArtvenusInfos::<T>::iter().map(|(id, _)| id).collect()
     }
@@ -0,0 +1,88 @@
+//! Storage migrations of the artvenuses.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::migration::{put_storage_value, StorageIterator},
+    traits::Get,
+    weights::Weight,
+    IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+};
+use sp_std::prelude::*;
+
+use ci_primitives::{ArtistId, SellCurrency, Text};
+
+use crate::{
+    AcquiredAt, AcquisitionType, ArtistArtvenuses, ArtistNumberOf, ArtvenusInfo, ContentHash,
+    HolderOf, ProvenanceNumbers, ProvenanceRecord, Provenances, Releases, StorageVersion, Trait,
+};
+
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    let version = StorageVersion::get();
+    if version == Releases::V4_0_0 {
+        return 0;
+    }
+    if version < Releases::V2_0_0 {
+        migrate_to_v2::<T>();
+    }
+    if version < Releases::V3_0_0 {
+        migrate_to_v3::<T>();
+    }
+    if version < Releases::V4_0_0 {
+        migrate_to_v4::<T>();
+    }
+    StorageVersion::put(Releases::V4_0_0);
+    T::MaximumBlockWeight::get()
+}
+
+/// `ArtvenusInfo` before the content hash is added.
+#[derive(Encode, Decode)]
+struct ArtvenusInfoV1<BlockNumber> {
+    origin: ArtistId,
+    time: BlockNumber,
+    name: Text,
+    desc: Text,
+}
+
+/// The artvenuses created before have caller-chosen ids, thus no content hash.
+fn migrate_to_v2<T: Trait>() {
+    let old: Vec<_> =
+        StorageIterator::<ArtvenusInfoV1<T::BlockNumber>>::new(b"Artvenuses", b"ArtvenusInfos")
+            .drain()
+            .collect();
+    for (key, info) in old {
+        let info = ArtvenusInfo::<_, ContentHash<T>> {
+            origin: info.origin,
+            time: info.time,
+            name: info.name,
+            desc: info.desc,
+            content_hash: None,
+        };
+        put_storage_value(b"Artvenuses", b"ArtvenusInfos", &key, info);
+    }
+}
+
+/// The provenance of the artvenuses created before begins with their current holders.
+fn migrate_to_v3<T: Trait>() {
+    let now = frame_system::Module::<T>::block_number();
+    for (id, (holder, _)) in HolderOf::<T>::iter() {
+        if ProvenanceNumbers::<T>::get(&id) > 0 {
+            continue;
+        }
+        let record = ProvenanceRecord {
+            holder,
+            block: AcquiredAt::<T>::get(&id).unwrap_or(now),
+            acquisition: AcquisitionType::Migrated,
+            price: None,
+            currency: SellCurrency::Native,
+        };
+        Provenances::<T>::insert(&id, 0, record);
+        ProvenanceNumbers::<T>::insert(&id, 1);
+    }
+}
+
+/// The artvenuses created before are indexed by their numbers of the artists.
+fn migrate_to_v4<T: Trait>() {
+    for (_, number, id) in ArtistArtvenuses::<T>::iter() {
+        ArtistNumberOf::<T>::insert(&id, number);
+    }
+}
@@ -0,0 +1,103 @@
+//! Test utilities
+
+#![cfg(test)]
+
+use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, Perbill};
+
+use ci_primitives::Text;
+use cirml_artists::{ArtistInfo, Gender};
+
+use crate::{Module, Trait};
+
+use frame_system as system;
+impl_outer_origin! {
+    pub enum Origin for Test {}
+}
+
+// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Test;
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::one();
+    pub const ExistentialDeposit: u64 = 1;
+}
+impl frame_system::Trait for Test {
+    type Origin = Origin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Call = ();
+    type Hash = H256;
+    type Hashing = ::sp_runtime::traits::BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = ();
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type ModuleToIndex = ();
+    type AccountData = cirml_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+}
+impl cirml_balances::Trait for Test {
+    type Balance = u64;
+    type DustRemoval = ();
+    type Event = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+}
+impl cirml_artists::Trait for Test {
+    type Event = ();
+}
+impl Trait for Test {
+    type Hash = H256;
+    type Event = ();
+    type Market = ();
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Artists = cirml_artists::Module<Test>;
+pub type Artvenuses = Module<Test>;
+
+pub const ARTIST: u64 = 1;
+pub const OTHER_ARTIST: u64 = 2;
+
+pub fn text(s: &str) -> Text {
+    s.as_bytes().to_vec()
+}
+
+/// `ARTIST` (id 0) and `OTHER_ARTIST` (id 1) are registered at block 1.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::default()
+        .build_storage::<Test>()
+        .unwrap();
+
+    let mut ext: sp_io::TestExternalities = t.into();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        for (account, name) in &[(ARTIST, "artist"), (OTHER_ARTIST, "other")] {
+            Artists::regist_artist(
+                system::RawOrigin::Root.into(),
+                *account,
+                ArtistInfo {
+                    name: text(name),
+                    gender: Gender::Male,
+                },
+            )
+            .unwrap();
+        }
+    });
+    ext
+}
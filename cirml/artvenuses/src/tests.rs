@@ -0,0 +1,125 @@
+//! Tests for the module.
+
+#![cfg(test)]
+
+use frame_support::{assert_noop, assert_ok, StorageDoubleMap, StorageMap, StorageValue};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+use ci_primitives::SellCurrency;
+
+use crate::mock::*;
+use crate::{
+    migration, AcquisitionType, ArtistNumberOf, Error, ProvenanceNumbers, ProvenanceRecord,
+    Provenances, Releases, StorageVersion,
+};
+
+#[test]
+fn artvenus_id_should_be_derived_from_content() {
+    new_test_ext().execute_with(|| {
+        let content = BlakeTwo256::hash(b"the content of venus");
+        assert_ok!(Artvenuses::create_artvenus_from_content(
+            Origin::signed(ARTIST),
+            content,
+            text("venus"),
+            text("")
+        ));
+
+        let id = BlakeTwo256::hash_of(&(0u32, content, text("venus"), text("")));
+        assert_eq!(
+            Artvenuses::derive_artvenus_id(0, &content, &text("venus"), &text("")),
+            id
+        );
+        assert_eq!(
+            Artvenuses::artvenus_infos(id).unwrap().content_hash,
+            Some(content)
+        );
+        assert_eq!(Artvenuses::holder_for(id).unwrap(), ARTIST);
+        assert_noop!(
+            Artvenuses::create_artvenus_from_content(
+                Origin::signed(ARTIST),
+                content,
+                text("venus"),
+                text("")
+            ),
+            Error::<Test>::ArtvenusAlreadyExist
+        );
+    });
+}
+
+#[test]
+fn squatted_derived_id_should_be_reclaimed_by_artist() {
+    new_test_ext().execute_with(|| {
+        let content = BlakeTwo256::hash(b"the content of venus");
+        let id = Artvenuses::derive_artvenus_id(0, &content, &text("venus"), &text(""));
+        assert_ok!(Artvenuses::create_artvenus(
+            Origin::signed(OTHER_ARTIST),
+            id,
+            text("venus"),
+            text("")
+        ));
+        // the squatting artist could not reclaim it by its own content
+        assert_noop!(
+            Artvenuses::create_artvenus(Origin::signed(ARTIST), id, text("venus"), text("")),
+            Error::<Test>::ArtvenusAlreadyExist
+        );
+
+        assert_ok!(Artvenuses::create_artvenus_from_content(
+            Origin::signed(ARTIST),
+            content,
+            text("venus"),
+            text("")
+        ));
+        let venus = Artvenuses::artvenus_infos(id).unwrap();
+        assert_eq!(venus.origin, 0);
+        assert_eq!(venus.content_hash, Some(content));
+        assert_eq!(Artvenuses::holder_for(id).unwrap(), ARTIST);
+        assert_eq!(Artvenuses::holder_artvenuses(OTHER_ARTIST, 0), None);
+        assert_eq!(Artvenuses::artist_artvenuses(1, 0), None);
+        assert_eq!(Artvenuses::provenance_numbers(id), 1);
+
+        // the derived artvenus could not be replaced again
+        assert_noop!(
+            Artvenuses::create_artvenus_from_content(
+                Origin::signed(ARTIST),
+                content,
+                text("venus"),
+                text("")
+            ),
+            Error::<Test>::ArtvenusAlreadyExist
+        );
+    });
+}
+
+#[test]
+fn migration_should_seed_provenance_and_artist_numbers() {
+    new_test_ext().execute_with(|| {
+        let id = H256::repeat_byte(1);
+        assert_ok!(Artvenuses::create_artvenus(
+            Origin::signed(ARTIST),
+            id,
+            text("venus"),
+            text("")
+        ));
+        // as created before the provenance is recorded
+        Provenances::<Test>::remove_prefix(&id);
+        ProvenanceNumbers::<Test>::remove(&id);
+        ArtistNumberOf::<Test>::remove(&id);
+        StorageVersion::put(Releases::V2_0_0);
+
+        System::set_block_number(5);
+        migration::on_runtime_upgrade::<Test>();
+        assert_eq!(
+            Artvenuses::provenance(id),
+            vec![ProvenanceRecord {
+                holder: ARTIST,
+                block: 1,
+                acquisition: AcquisitionType::Migrated,
+                price: None,
+                currency: SellCurrency::Native,
+            }]
+        );
+        assert_eq!(Artvenuses::artist_number_of(id), Some(0));
+        assert_eq!(StorageVersion::get(), Releases::V4_0_0);
+    });
+}
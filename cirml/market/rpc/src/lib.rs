@@ -9,7 +9,11 @@ use serde::Serialize;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 
-use cirml_market_runtime_api::{MarketApi as MarketRuntimeApi, OnSellInfo, OnSellState};
+use ci_primitives::ArtistId;
+use cirml_market_runtime_api::{
+    DealQuote, MarketApi as MarketRuntimeApi, OfferInfo, OnSellInfo, OnSellState, SaleRecord,
+    SalesStats, SellCurrency,
+};
 
 pub struct Market<C, B> {
     client: Arc<C>,
@@ -27,31 +31,58 @@ impl<C, B> Market<C, B> {
 }
 
 #[rpc]
-pub trait MarketApi<BlockHash, ArtvenusId, Balance, BlockNumber> {
+pub trait MarketApi<BlockHash, AccountId, ArtvenusId, Balance, BlockNumber> {
+    /// Private sell orders are hidden if `public_only` is set, expired ones are always hidden.
     #[rpc(name = "market_getOnSells")]
-    fn on_sell(&self, at: Option<BlockHash>) -> Result<serde_json::Value>;
+    fn on_sell(
+        &self,
+        at: Option<BlockHash>,
+        public_only: Option<bool>,
+    ) -> Result<serde_json::Value>;
+
+    #[rpc(name = "market_quoteDeal")]
+    fn quote_deal(&self, venus_id: ArtvenusId, at: Option<BlockHash>) -> Result<serde_json::Value>;
+
+    #[rpc(name = "market_getOffers")]
+    fn offers(&self, venus_id: ArtvenusId, at: Option<BlockHash>) -> Result<serde_json::Value>;
+
+    #[rpc(name = "market_getLastSale")]
+    fn last_sale(&self, venus_id: ArtvenusId, at: Option<BlockHash>) -> Result<serde_json::Value>;
+
+    #[rpc(name = "market_getArtistSales")]
+    fn artist_sales(&self, artist_id: ArtistId, at: Option<BlockHash>)
+        -> Result<serde_json::Value>;
 }
 
-impl<C, Block, ArtvenusId, Balance, BlockNumber>
-    MarketApi<<Block as BlockT>::Hash, ArtvenusId, Balance, BlockNumber> for Market<C, Block>
+impl<C, Block, AccountId, ArtvenusId, Balance, BlockNumber>
+    MarketApi<<Block as BlockT>::Hash, AccountId, ArtvenusId, Balance, BlockNumber>
+    for Market<C, Block>
 where
     C: sp_api::ProvideRuntimeApi<Block>,
     C: HeaderBackend<Block>,
     C: Send + Sync + 'static,
-    C::Api: MarketRuntimeApi<Block, ArtvenusId, Balance, BlockNumber>,
+    C::Api: MarketRuntimeApi<Block, AccountId, ArtvenusId, Balance, BlockNumber>,
     Block: BlockT,
+    AccountId:
+        Clone + std::hash::Hash + std::cmp::Eq + std::fmt::Display + Codec + serde::Serialize,
     ArtvenusId:
         Clone + std::hash::Hash + std::cmp::Eq + std::fmt::Display + Codec + serde::Serialize,
     Balance: Clone + std::fmt::Display + Codec + serde::Serialize + ToString,
     BlockNumber: Clone + std::fmt::Display + Codec + serde::Serialize,
 {
-    fn on_sell(&self, at: Option<<Block as BlockT>::Hash>) -> Result<serde_json::Value> {
+    fn on_sell(
+        &self,
+        at: Option<<Block as BlockT>::Hash>,
+        public_only: Option<bool>,
+    ) -> Result<serde_json::Value> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
-        let r: HashMap<ArtvenusId, OnSellInfoForRpc<BlockNumber>> = api
+        let public_only = public_only.unwrap_or(false);
+        let r: HashMap<ArtvenusId, OnSellInfoForRpc<AccountId, BlockNumber>> = api
             .on_sell(&at)
             .map(|list| {
                 list.into_iter()
+                    .filter(|(_, info)| !public_only || info.buyer.is_none())
                     .map(|(id, info)| (id, info.into()))
                     .collect()
             })
@@ -59,24 +90,192 @@ where
         let map = serde_json::value::to_value(r).map_err(serde_error_into_rpc_err)?;
         Ok(map)
     }
+
+    fn quote_deal(
+        &self,
+        venus_id: ArtvenusId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<serde_json::Value> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let r: Option<DealQuoteForRpc<AccountId>> = api
+            .quote_deal(&at, venus_id)
+            .map(|quote| quote.map(Into::into))
+            .map_err(runtime_error_into_rpc_err)?;
+        let quote = serde_json::value::to_value(r).map_err(serde_error_into_rpc_err)?;
+        Ok(quote)
+    }
+
+    fn offers(
+        &self,
+        venus_id: ArtvenusId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<serde_json::Value> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let r: HashMap<AccountId, OfferInfoForRpc<BlockNumber>> = api
+            .offers(&at, venus_id)
+            .map(|list| {
+                list.into_iter()
+                    .map(|(who, info)| (who, info.into()))
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)?;
+        let map = serde_json::value::to_value(r).map_err(serde_error_into_rpc_err)?;
+        Ok(map)
+    }
+
+    fn last_sale(
+        &self,
+        venus_id: ArtvenusId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<serde_json::Value> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let r: Option<SaleRecordForRpc<AccountId, BlockNumber>> = api
+            .last_sale(&at, venus_id)
+            .map(|record| record.map(Into::into))
+            .map_err(runtime_error_into_rpc_err)?;
+        let record = serde_json::value::to_value(r).map_err(serde_error_into_rpc_err)?;
+        Ok(record)
+    }
+
+    fn artist_sales(
+        &self,
+        artist_id: ArtistId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<serde_json::Value> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        let r: SalesStatsForRpc = api
+            .artist_sales(&at, artist_id)
+            .map(Into::into)
+            .map_err(runtime_error_into_rpc_err)?;
+        let stats = serde_json::value::to_value(r).map_err(serde_error_into_rpc_err)?;
+        Ok(stats)
+    }
 }
 
 #[derive(Serialize)]
-struct OnSellInfoForRpc<BlockNumber> {
+struct OnSellInfoForRpc<AccountId, BlockNumber> {
     state: OnSellState,
     price: String,
     time: BlockNumber,
+    buyer: Option<AccountId>,
+    expire: Option<BlockNumber>,
+    currency: SellCurrency,
 }
 
-impl<BlockNumber, Balance> From<OnSellInfo<Balance, BlockNumber>> for OnSellInfoForRpc<BlockNumber>
+impl<AccountId, BlockNumber, Balance> From<OnSellInfo<AccountId, Balance, BlockNumber>>
+    for OnSellInfoForRpc<AccountId, BlockNumber>
 where
     Balance: ToString,
 {
-    fn from(runtime_info: OnSellInfo<Balance, BlockNumber>) -> Self {
+    fn from(runtime_info: OnSellInfo<AccountId, Balance, BlockNumber>) -> Self {
         OnSellInfoForRpc {
             state: runtime_info.state,
             price: runtime_info.price.to_string(),
             time: runtime_info.time,
+            buyer: runtime_info.buyer,
+            expire: runtime_info.expire,
+            currency: runtime_info.currency,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct OfferInfoForRpc<BlockNumber> {
+    price: String,
+    expire: BlockNumber,
+}
+
+impl<BlockNumber, Balance> From<OfferInfo<Balance, BlockNumber>> for OfferInfoForRpc<BlockNumber>
+where
+    Balance: ToString,
+{
+    fn from(runtime_info: OfferInfo<Balance, BlockNumber>) -> Self {
+        OfferInfoForRpc {
+            price: runtime_info.price.to_string(),
+            expire: runtime_info.expire,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DealQuoteForRpc<AccountId> {
+    price: String,
+    is_virgin_sell: bool,
+    platform: (AccountId, String),
+    royalty: (AccountId, String),
+    seller: (AccountId, String),
+    beneficiaries: Vec<(AccountId, String)>,
+    commission: Option<(AccountId, String)>,
+}
+
+impl<AccountId, Balance> From<DealQuote<AccountId, Balance>> for DealQuoteForRpc<AccountId>
+where
+    Balance: ToString,
+{
+    fn from(quote: DealQuote<AccountId, Balance>) -> Self {
+        let part = |(who, value): (AccountId, Balance)| (who, value.to_string());
+        DealQuoteForRpc {
+            price: quote.price.to_string(),
+            is_virgin_sell: quote.is_virgin_sell,
+            platform: part(quote.platform),
+            royalty: part(quote.royalty),
+            seller: part(quote.seller),
+            beneficiaries: quote.beneficiaries.into_iter().map(part).collect(),
+            commission: quote.commission.map(part),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SaleRecordForRpc<AccountId, BlockNumber> {
+    price: String,
+    currency: SellCurrency,
+    block: BlockNumber,
+    buyer: AccountId,
+    seller: AccountId,
+    is_virgin_sell: bool,
+}
+
+impl<AccountId, Balance, BlockNumber> From<SaleRecord<AccountId, Balance, BlockNumber>>
+    for SaleRecordForRpc<AccountId, BlockNumber>
+where
+    Balance: ToString,
+{
+    fn from(record: SaleRecord<AccountId, Balance, BlockNumber>) -> Self {
+        SaleRecordForRpc {
+            price: record.price.to_string(),
+            currency: record.currency,
+            block: record.block,
+            buyer: record.buyer,
+            seller: record.seller,
+            is_virgin_sell: record.is_virgin_sell,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SalesStatsForRpc {
+    volume: String,
+    stable_volume: String,
+    count: u64,
+}
+
+impl<Balance> From<SalesStats<Balance>> for SalesStatsForRpc
+where
+    Balance: ToString,
+{
+    fn from(stats: SalesStats<Balance>) -> Self {
+        SalesStatsForRpc {
+            volume: stats.volume.to_string(),
+            stable_volume: stats.stable_volume.to_string(),
+            count: stats.count,
         }
     }
 }
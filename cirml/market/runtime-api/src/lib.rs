@@ -4,15 +4,28 @@ use codec::Codec;
 
 use sp_std::prelude::Vec;
 
+use ci_primitives::ArtistId;
+
 // re-export
-pub use cirml_market::{OnSellInfo, OnSellState};
+pub use cirml_market::{
+    DealQuote, OfferInfo, OnSellInfo, OnSellState, SaleRecord, SalesStats, SellCurrency,
+};
 
 sp_api::decl_runtime_apis! {
-    pub trait MarketApi<ArtvenusId, Balance, BlockNumber> where
+    pub trait MarketApi<AccountId, ArtvenusId, Balance, BlockNumber> where
+        AccountId: Codec,
         ArtvenusId: Codec,
         Balance: Codec,
         BlockNumber: Codec,
     {
-        fn on_sell() -> Vec<(ArtvenusId, OnSellInfo<Balance, BlockNumber>)>;
+        fn on_sell() -> Vec<(ArtvenusId, OnSellInfo<AccountId, Balance, BlockNumber>)>;
+
+        fn quote_deal(venus_id: ArtvenusId) -> Option<DealQuote<AccountId, Balance>>;
+
+        fn offers(venus_id: ArtvenusId) -> Vec<(AccountId, OfferInfo<Balance, BlockNumber>)>;
+
+        fn last_sale(venus_id: ArtvenusId) -> Option<SaleRecord<AccountId, Balance, BlockNumber>>;
+
+        fn artist_sales(artist_id: ArtistId) -> SalesStats<Balance>;
     }
 }
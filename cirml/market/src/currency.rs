@@ -0,0 +1,90 @@
+//! The currencies to settle a sell order in.
+//!
+//! Sell orders, dutch auctions and lots could be in any `SellCurrency`. Auctions, raffles, offers
+//! and swaps are in `SellCurrency::Native` only: they keep the balances of the bidders, entrants
+//! and offerers reserved across blocks, and the refunds in `on_finalize` and on burn, and of the
+//! outbid, are done in the native currency.
+
+use frame_support::traits::{BalanceStatus, Currency, ReservableCurrency};
+use sp_runtime::{DispatchError, DispatchResult};
+
+pub use ci_primitives::SellCurrency;
+
+use crate::{Module, Trait};
+
+type NativeCurrency<T> = cirml_balances::Module<T>;
+
+impl<T: Trait> Module<T> {
+    pub(crate) fn free_balance_in(currency: SellCurrency, who: &T::AccountId) -> T::Balance {
+        match currency {
+            SellCurrency::Native => <NativeCurrency<T> as Currency<_>>::free_balance(who),
+            SellCurrency::Stable => T::StableCurrency::free_balance(who),
+        }
+    }
+
+    pub(crate) fn total_balance_in(currency: SellCurrency, who: &T::AccountId) -> T::Balance {
+        match currency {
+            SellCurrency::Native => <NativeCurrency<T> as Currency<_>>::total_balance(who),
+            SellCurrency::Stable => T::StableCurrency::total_balance(who),
+        }
+    }
+
+    pub(crate) fn reserved_balance_in(currency: SellCurrency, who: &T::AccountId) -> T::Balance {
+        match currency {
+            SellCurrency::Native => {
+                <NativeCurrency<T> as ReservableCurrency<_>>::reserved_balance(who)
+            }
+            SellCurrency::Stable => T::StableCurrency::reserved_balance(who),
+        }
+    }
+
+    pub(crate) fn can_reserve_in(
+        currency: SellCurrency,
+        who: &T::AccountId,
+        value: T::Balance,
+    ) -> bool {
+        match currency {
+            SellCurrency::Native => {
+                <NativeCurrency<T> as ReservableCurrency<_>>::can_reserve(who, value)
+            }
+            SellCurrency::Stable => T::StableCurrency::can_reserve(who, value),
+        }
+    }
+
+    pub(crate) fn reserve_in(
+        currency: SellCurrency,
+        who: &T::AccountId,
+        value: T::Balance,
+    ) -> DispatchResult {
+        match currency {
+            SellCurrency::Native => {
+                <NativeCurrency<T> as ReservableCurrency<_>>::reserve(who, value)
+            }
+            SellCurrency::Stable => T::StableCurrency::reserve(who, value),
+        }
+    }
+
+    pub(crate) fn repatriate_reserved_in(
+        currency: SellCurrency,
+        slashed: &T::AccountId,
+        beneficiary: &T::AccountId,
+        value: T::Balance,
+    ) -> Result<T::Balance, DispatchError> {
+        match currency {
+            SellCurrency::Native => {
+                <NativeCurrency<T> as ReservableCurrency<_>>::repatriate_reserved(
+                    slashed,
+                    beneficiary,
+                    value,
+                    BalanceStatus::Free,
+                )
+            }
+            SellCurrency::Stable => T::StableCurrency::repatriate_reserved(
+                slashed,
+                beneficiary,
+                value,
+                BalanceStatus::Free,
+            ),
+        }
+    }
+}
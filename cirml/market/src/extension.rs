@@ -0,0 +1,82 @@
+//! Refuse the market transactions at the pool level while the market is paused.
+
+use codec::{Decode, Encode};
+use frame_support::traits::IsSubType;
+use sp_runtime::{
+    traits::{DispatchInfoOf, SignedExtension},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+};
+use sp_std::marker::PhantomData;
+
+use crate::{Call, Module, Trait};
+
+/// The custom code of `InvalidTransaction` for a market transaction while the market is paused.
+pub const MARKET_PAUSED: u8 = 1;
+
+/// Only the calls of the manager or root and the calls to cancel or refund are valid in the market
+/// calls while the market is paused.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct CheckMarketPaused<T: Trait + Send + Sync>(PhantomData<T>);
+
+impl<T: Trait + Send + Sync> CheckMarketPaused<T> {
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: Trait + Send + Sync> sp_std::fmt::Debug for CheckMarketPaused<T> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "CheckMarketPaused")
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+impl<T: Trait + Send + Sync> SignedExtension for CheckMarketPaused<T>
+where
+    <T as frame_system::Trait>::Call: IsSubType<Module<T>, T>,
+{
+    const IDENTIFIER: &'static str = "CheckMarketPaused";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Trait>::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        _who: &Self::AccountId,
+        call: &Self::Call,
+        _info: &DispatchInfoOf<Self::Call>,
+        _len: usize,
+    ) -> TransactionValidity {
+        if !Module::<T>::paused() {
+            return Ok(ValidTransaction::default());
+        }
+        match call.is_sub_type() {
+            Some(Call::set_paused(..))
+            | Some(Call::set_manager(..))
+            | Some(Call::set_gallery(..))
+            | Some(Call::set_resale_cooldown(..))
+            | Some(Call::set_fee_schedule(..))
+            | Some(Call::set_virgin_sell_percent(..))
+            | Some(Call::set_normal_sell_percent(..))
+            | Some(Call::cancel_sell(..))
+            | Some(Call::withdraw_offer(..))
+            | Some(Call::cancel_lot(..))
+            | Some(Call::cancel_swap(..))
+            | Some(Call::revoke_consign(..))
+            | None => Ok(ValidTransaction::default()),
+            Some(_) => InvalidTransaction::Custom(MARKET_PAUSED).into(),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+//! Fee schedule of the market, and the payout breakdown of a deal.
+
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use codec::{Decode, Encode};
+
+use sp_runtime::{
+    traits::{AtLeast32Bit, Saturating},
+    PerThing, Percent, RuntimeDebug,
+};
+use sp_std::prelude::*;
+
+/// The max number of extra beneficiaries in a fee schedule.
+pub const MAX_BENEFICIARIES: usize = 8;
+
+/// How the price of a deal is shared, the seller receives what is left by the shares.
+///
+/// In a virgin sell the artist is the seller.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct FeeSchedule<AccountId> {
+    /// share for the platform, paid to the `Manager`
+    pub platform: Percent,
+    /// share for the artist of the venus
+    pub royalty: Percent,
+    /// shares for extra beneficiaries
+    pub beneficiaries: Vec<(AccountId, Percent)>,
+}
+
+impl<AccountId> FeeSchedule<AccountId> {
+    /// Sum of all shares, `None` if it's over 100%.
+    pub fn total(&self) -> Option<Percent> {
+        let total = self.beneficiaries.iter().fold(
+            self.platform.deconstruct() as u32 + self.royalty.deconstruct() as u32,
+            |acc, (_, share)| acc + share.deconstruct() as u32,
+        );
+        if total > 100 {
+            None
+        } else {
+            Some(Percent::from_percent(total as u8))
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.beneficiaries.len() <= MAX_BENEFICIARIES && self.total().is_some()
+    }
+
+    /// Whether the shares with `extra` are still no more than 100%.
+    pub fn can_add(&self, extra: Percent) -> bool {
+        self.total()
+            .and_then(|total| total.deconstruct().checked_add(extra.deconstruct()))
+            .map_or(false, |total| total <= 100)
+    }
+}
+
+/// The payout breakdown of a deal, every part is `(receiver, value)`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DealQuote<AccountId, Balance> {
+    pub price: Balance,
+    pub is_virgin_sell: bool,
+    pub platform: (AccountId, Balance),
+    pub royalty: (AccountId, Balance),
+    pub seller: (AccountId, Balance),
+    pub beneficiaries: Vec<(AccountId, Balance)>,
+    /// the consignee selling on behalf of the seller
+    pub commission: Option<(AccountId, Balance)>,
+}
+
+impl<AccountId: Clone, Balance: AtLeast32Bit + Copy> DealQuote<AccountId, Balance> {
+    /// Quote a deal at `price` with `schedule`, and `commission` for the consignee if any.
+    ///
+    /// Every share is rounded down and the seller receives the rest, thus the parts of a quote
+    /// always add up to `price`. Return `None` if the shares are over the price, which means
+    /// the schedule is invalid.
+    pub fn new(
+        price: Balance,
+        is_virgin_sell: bool,
+        schedule: FeeSchedule<AccountId>,
+        manager: AccountId,
+        artist: AccountId,
+        seller: AccountId,
+        commission: Option<(AccountId, Percent)>,
+    ) -> Option<Self> {
+        let platform = schedule.platform.mul_floor(price);
+        let royalty = schedule.royalty.mul_floor(price);
+        let beneficiaries: Vec<(AccountId, Balance)> = schedule
+            .beneficiaries
+            .into_iter()
+            .map(|(who, share)| (who, share.mul_floor(price)))
+            .collect();
+        let commission = commission.map(|(who, share)| (who, share.mul_floor(price)));
+        let shared = beneficiaries
+            .iter()
+            .chain(commission.iter())
+            .fold(platform.saturating_add(royalty), |acc, (_, value)| {
+                acc.saturating_add(*value)
+            });
+        let for_seller = price.checked_sub(&shared)?;
+        Some(DealQuote {
+            price,
+            is_virgin_sell,
+            platform: (manager, platform),
+            royalty: (artist, royalty),
+            seller: (seller, for_seller),
+            beneficiaries,
+            commission,
+        })
+    }
+
+    /// All receivers of the deal with their parts.
+    pub fn receivers(&self) -> Vec<(AccountId, Balance)> {
+        let mut receivers = vec![
+            self.platform.clone(),
+            self.royalty.clone(),
+            self.seller.clone(),
+        ];
+        receivers.extend(self.beneficiaries.iter().cloned());
+        receivers.extend(self.commission.iter().cloned());
+        receivers
+    }
+}
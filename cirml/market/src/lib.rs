@@ -1,36 +1,162 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+mod currency;
+mod extension;
+mod fee;
+mod migration;
+mod mock;
+mod tests;
+
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
 use codec::{Decode, Encode};
 
-use sp_runtime::{DispatchError, DispatchResult, Percent, RuntimeDebug};
-use sp_std::prelude::Vec;
+use sp_runtime::{
+    traits::{
+        BadOrigin, CheckedAdd, Hash, One, SaturatedConversion, Saturating, StaticLookup, Zero,
+    },
+    DispatchError, DispatchResult, PerThing, Percent, RuntimeDebug,
+};
+use sp_std::prelude::*;
 
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    traits::{Currency, ExistenceRequirement::KeepAlive},
-    IterableStorageMap,
+    traits::{Get, Randomness, ReservableCurrency},
+    weights::Weight,
+    IterableStorageDoubleMap, IterableStorageMap,
 };
-use frame_system::{self as system, ensure_signed};
+use frame_system::{self as system, ensure_root, ensure_signed};
+
+use ci_primitives::ArtistId;
+use cirml_artvenuses::{AcquisitionType, Artvenus, ArtvenusId};
+
+pub use currency::SellCurrency;
+pub use extension::CheckMarketPaused;
+pub use fee::{DealQuote, FeeSchedule, MAX_BENEFICIARIES};
 
-use cirml_artvenuses::{Artvenus, ArtvenusId};
+pub type LotId = u64;
+
+/// The most venuses in a lot.
+pub const MAX_LOT_VENUSES: usize = 16;
+/// The most storage reads, as well as writes, in the settlement of a deal.
+const SETTLE_READS_WRITES: Weight = 16;
+
+pub type SwapId = u64;
+
+pub type HashOf<T> = <T as frame_system::Trait>::Hash;
 
 pub trait Trait:
     frame_system::Trait + cirml_artists::Trait + cirml_artvenuses::Trait + cirml_balances::Trait
 {
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+    /// The second currency to sell in, e.g. another instance of `cirml_balances`.
+    type StableCurrency: ReservableCurrency<Self::AccountId, Balance = Self::Balance>;
+
+    /// The least part for the artist in virgin sell.
+    type MinVirginSellPercent: Get<Percent>;
+
+    /// The most part for the artist (royalty) in normal sell.
+    type MaxNormalSellPercent: Get<Percent>;
+
+    /// The most expired sell orders removed in a block.
+    type MaxExpiredSellsPerBlock: Get<u32>;
+
+    /// The randomness to draw the winner of a raffle.
+    type Randomness: Randomness<<Self as frame_system::Trait>::Hash>;
+
+    /// The most entrants in a raffle.
+    type MaxRaffleEntrants: Get<u32>;
+
+    /// The least valuation of a venus in a swap.
+    type MinSwapValuation: Get<Self::Balance>;
+
+    /// The most auctions and raffles ending in a block, they are all settled in `on_finalize`.
+    type MaxEndingsPerBlock: Get<u32>;
+
+    /// The least price of an offer.
+    type MinOfferPrice: Get<Self::Balance>;
+
+    /// The most offers for a venus, they are all refunded once one of them is accepted.
+    type MaxOffersPerVenus: Get<u32>;
 }
 
 decl_event!(
 	pub enum Event<T> where
 		<T as frame_system::Trait>::AccountId,
+		<T as frame_system::Trait>::BlockNumber,
 		<T as cirml_balances::Trait>::Balance,
 		ArtvenusId = ArtvenusId<T>,
+		HashOf = HashOf<T>,
 	{
 	    OnSell(AccountId, ArtvenusId, Balance),
-	    Deal(AccountId, ArtvenusId, bool),
+	    /// A venus is dealt (payer, recipient, venus, is virgin sell).
+	    Deal(AccountId, AccountId, ArtvenusId, bool),
+	    /// A sell order is cancelled by the seller.
+	    CancelSell(AccountId, ArtvenusId),
+	    /// The price of a sell order is updated (seller, venus, new price).
+	    UpdatePrice(AccountId, ArtvenusId, Balance),
+	    /// An english auction is started (seller, venus, reserve price, end block).
+	    AuctionStart(AccountId, ArtvenusId, Balance, BlockNumber),
+	    /// A new highest bid is placed (bidder, venus, price).
+	    Bid(AccountId, ArtvenusId, Balance),
+	    /// An auction is closed without a deal.
+	    AuctionFail(ArtvenusId),
+	    /// The fee schedule for virgin sell (true) or normal sell (false) is updated.
+	    FeeScheduleUpdate(bool),
+	    /// The manager is changed.
+	    ManagerUpdate(AccountId),
+	    /// The part for the artist in virgin sell is changed.
+	    VirginSellPercentUpdate(Percent),
+	    /// The part for the artist in normal sell is changed.
+	    NormalSellPercentUpdate(Percent),
+	    /// A gallery is registered (true) or unregistered (false).
+	    GalleryUpdate(AccountId, bool),
+	    /// A venus is consigned (holder, venus, consignee, commission).
+	    Consign(AccountId, ArtvenusId, AccountId, Percent),
+	    /// A consignment is revoked by the holder.
+	    ConsignRevoke(AccountId, ArtvenusId),
+	    /// The market is paused (true) or resumed (false).
+	    PauseUpdate(bool),
+	    /// A sell order is removed for expiry.
+	    SellExpire(ArtvenusId),
+	    /// A dutch auction is started (seller, venus, start price, floor price).
+	    DutchAuctionStart(AccountId, ArtvenusId, Balance, Balance),
+	    /// A presale phase is set by an artist (artist, start block, end block).
+	    PresaleUpdate(ArtistId, BlockNumber, BlockNumber),
+	    /// The presale phase of an artist is removed.
+	    PresaleClear(ArtistId),
+	    /// An account proves it's allowed in the presale phase of an artist (account, artist, root).
+	    PresaleJoin(AccountId, ArtistId, HashOf),
+	    /// The resale cooldown of the market is updated.
+	    ResaleCooldownUpdate(BlockNumber),
+	    /// The resale cooldown of the venuses of an artist is set or removed.
+	    ArtistCooldownUpdate(ArtistId, Option<BlockNumber>),
+	    /// A raffle is started (seller, venus, price, end block).
+	    RaffleStart(AccountId, ArtvenusId, Balance, BlockNumber),
+	    /// An account enters a raffle and the price is reserved.
+	    RaffleEnter(AccountId, ArtvenusId),
+	    /// The winner of a raffle is drawn and the venus is dealt.
+	    RaffleWin(AccountId, ArtvenusId),
+	    /// A raffle is closed without a deal, all entrants are refunded.
+	    RaffleFail(ArtvenusId),
+	    /// An offer is placed (buyer, venus, price, expire block).
+	    Offer(AccountId, ArtvenusId, Balance, BlockNumber),
+	    /// An offer is cancelled and the reserved price is refunded.
+	    OfferCancel(AccountId, ArtvenusId),
+	    /// A lot is put on sell (seller, lot, price).
+	    LotOnSell(AccountId, LotId, Balance),
+	    /// A lot is dealt (buyer, lot), every venus in it has its own `Deal` event.
+	    LotDeal(AccountId, LotId),
+	    /// A lot is cancelled by the seller.
+	    LotCancel(AccountId, LotId),
+	    /// A swap is proposed (proposer, swap, venus to give, venus to take, top-up).
+	    SwapPropose(AccountId, SwapId, ArtvenusId, ArtvenusId, Balance),
+	    /// A swap is accepted (proposer, accepter, swap).
+	    SwapDone(AccountId, AccountId, SwapId),
+	    /// A swap is cancelled and the reserved balance is refunded.
+	    SwapCancel(AccountId, SwapId),
 	}
 );
 
@@ -46,6 +172,104 @@ decl_error! {
         NotHolderInSell,
         ///
         CantPay,
+        /// The venus is in an english auction or a raffle, it could not be bought directly.
+        InBidding,
+        /// The venus is not in an english auction.
+        NotInBidding,
+        /// The auction end block must be after the current block.
+        InvalidAuctionEnd,
+        /// The auction is already ended.
+        AuctionEnded,
+        /// The bid is lower than the reserve price or the current highest bid.
+        BidTooLow,
+        /// The seller could not bid for its own auction.
+        BidOwnAuction,
+        /// The receiver of a settlement does not exist.
+        ReceiverNotExist,
+        /// Only the seller could change the sell order.
+        NotSeller,
+        /// Got an overflow when computing or paying the price.
+        Overflow,
+        /// The shares of a fee schedule are over 100%, or too many beneficiaries.
+        InvalidFeeSchedule,
+        /// The percent is out of the bounds set by the runtime.
+        PercentOutOfBounds,
+        /// The expire block must be after the current block.
+        InvalidExpire,
+        /// The offer does not exist.
+        OfferNotExist,
+        /// The offer is expired.
+        OfferExpired,
+        /// Only the holder could accept an offer.
+        NotHolder,
+        /// The holder could not make an offer for its own venus.
+        OfferOwnVenus,
+        /// The floor price is over the start price, or the decay step is zero.
+        InvalidDutchAuction,
+        /// The price of the sell order is over the max price of the buyer.
+        PriceTooHigh,
+        /// The sell order is changed since the buyer read it.
+        SellChanged,
+        /// The sell order is private for another buyer.
+        NotDesignatedBuyer,
+        /// The sell order is expired.
+        SellExpired,
+        /// The lot is empty, too large or has a venus more than once.
+        InvalidLot,
+        /// The lot does not exist.
+        LotNotExist,
+        /// The venus is in a lot.
+        InLot,
+        /// The market is paused.
+        MarketPaused,
+        /// The venus is not in a raffle.
+        NotInRaffle,
+        /// The entry window of the raffle is closed.
+        RaffleEnded,
+        /// The account has entered the raffle.
+        AlreadyEntered,
+        /// The raffle has the most entrants.
+        RaffleFull,
+        /// The seller could not enter its own raffle.
+        EnterOwnRaffle,
+        /// The presale phase is ended before started or allows no purchase.
+        InvalidPresale,
+        /// The artist has no presale phase or it's allowed by a list.
+        NoMerklePresale,
+        /// The merkle proof does not lead to the root of the presale phase.
+        InvalidMerkleProof,
+        /// The buyer is not allowed in the presale phase.
+        NotInAllowlist,
+        /// The buyer has made the most purchases in the presale phase.
+        PresaleLimitReached,
+        /// The holder could not sell the venus until the resale cooldown is over.
+        InCooldown,
+        /// The consignee is not a registered gallery.
+        NotGallery,
+        /// The commission with the fee schedule is over 100%.
+        InvalidCommission,
+        /// The venus is not consigned.
+        NotConsigned,
+        /// The swap does not exist.
+        SwapNotExist,
+        /// The venus could not be swapped with itself or one of the caller's own.
+        InvalidSwap,
+        /// The venus has not been sold in virgin sell, it could not be swapped.
+        VirginInSwap,
+        /// The valuation is under the least swap valuation or the last sale price of the venus.
+        ValuationTooLow,
+        /// The most auctions and raffles already end at the block.
+        TooManyEndings,
+        /// The offer price is under the least offer price.
+        OfferTooLow,
+        /// The venus has the most offers.
+        TooManyOffers,
+        /// The venus is last sold in `SellCurrency::Stable`, it could not be swapped.
+        StableInSwap,
+        /// The presale phase of the artist is not started.
+        PresaleNotStarted,
+        /// The seller or the holder could not deal its own venus.
+        DealOwnVenus,
     }
 }
 
@@ -55,147 +279,2107 @@ pub enum OnSellState {
     VirginSell,
     Sell,
     Bidding,
+    Raffle,
+}
+
+impl OnSellState {
+    /// The balances of the buyers are reserved in an auction or a raffle, the sell order could
+    /// not be dealt, changed or cancelled directly.
+    pub fn is_reserving(&self) -> bool {
+        *self == OnSellState::Bidding || *self == OnSellState::Raffle
+    }
 }
 
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
-pub struct OnSellInfo<Balance, BlockNumber> {
+pub struct OnSellInfo<AccountId, Balance, BlockNumber> {
     pub state: OnSellState,
     pub price: Balance,
     pub time: BlockNumber,
+    /// only the designated buyer could deal a private sell order
+    pub buyer: Option<AccountId>,
+    /// the sell order could not be dealt since the block
+    pub expire: Option<BlockNumber>,
+    /// the price is paid in the currency
+    pub currency: SellCurrency,
+    /// the account which puts the sell order, the holder or its consignee
+    pub lister: AccountId,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AuctionInfo<AccountId, Balance, BlockNumber> {
+    pub end: BlockNumber,
+    /// current highest bidder and the price, the price is reserved from the bidder
+    pub bid: Option<(AccountId, Balance)>,
+}
+
+/// How the price of a dutch auction decreases from the start price (`OnSellInfo.price`) since
+/// the sell order is put (`OnSellInfo.time`).
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum PriceDecay<Balance, BlockNumber> {
+    /// decrease by the amount every block
+    Linear(Balance),
+    /// decrease by the amount every the number of blocks
+    Step(BlockNumber, Balance),
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DutchInfo<Balance, BlockNumber> {
+    /// the price never decreases below the floor
+    pub floor: Balance,
+    pub decay: PriceDecay<Balance, BlockNumber>,
+}
+
+/// Several venuses sold together, each with its part of the price for the fees and royalty.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct LotInfo<AccountId, ArtvenusId, Balance, BlockNumber> {
+    pub seller: AccountId,
+    pub venuses: Vec<(ArtvenusId, Balance)>,
+    /// the sum of the parts
+    pub price: Balance,
+    pub time: BlockNumber,
+    pub currency: SellCurrency,
+}
+
+/// The proposer gives `give` and pays `top_up` for `take`.
+///
+/// The royalty of `give` is charged on `valuation` and paid by the proposer, the royalty of
+/// `take` is charged on `valuation + top_up` and paid by the accepter.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SwapInfo<AccountId, ArtvenusId, Balance> {
+    pub proposer: AccountId,
+    pub give: ArtvenusId,
+    pub take: ArtvenusId,
+    pub top_up: Balance,
+    pub valuation: Balance,
+    /// the royalty of `give`, it's reserved from the proposer with the top-up
+    pub royalty: Balance,
+}
+
+/// A gallery sells a venus on behalf of the holder for the commission.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct ConsignInfo<AccountId> {
+    pub consignee: AccountId,
+    /// the part of the price for the consignee in a deal
+    pub commission: Percent,
+}
+
+/// The record of a deal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SaleRecord<AccountId, Balance, BlockNumber> {
+    pub price: Balance,
+    pub currency: SellCurrency,
+    pub block: BlockNumber,
+    pub buyer: AccountId,
+    pub seller: AccountId,
+    pub is_virgin_sell: bool,
+}
+
+/// The aggregates of the deals of the venuses of an artist.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct SalesStats<Balance> {
+    /// the total price in `SellCurrency::Native`
+    pub volume: Balance,
+    /// the total price in `SellCurrency::Stable`
+    pub stable_volume: Balance,
+    pub count: u64,
+}
+
+/// The accounts allowed in a presale phase.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Allowlist<AccountId, Hash> {
+    List(Vec<AccountId>),
+    /// the root of a merkle tree of `T::Hashing::hash_of(&account)`, the pairs are hashed in
+    /// order, an account proves it's allowed by `join_presale`
+    MerkleRoot(Hash),
+}
+
+/// The virgin sells of the venuses of an artist are dealt to the allowed accounts only in a
+/// presale phase.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct PresaleInfo<AccountId, Hash, BlockNumber> {
+    pub allowlist: Allowlist<AccountId, Hash>,
+    pub start: BlockNumber,
+    pub end: BlockNumber,
+    /// the most purchases of an account in the phase
+    pub max_purchases: u32,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct RaffleInfo<AccountId, Balance, BlockNumber> {
+    /// the winner is drawn at the end of the block, the entries are closed since the block
+    pub end: BlockNumber,
+    /// the price is reserved from every entrant
+    pub entrants: Vec<AccountId>,
+    /// the price of the raffle, kept for the refunds even if the sell order is gone
+    pub price: Balance,
+}
+
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OfferInfo<Balance, BlockNumber> {
+    /// the price is reserved from the buyer
+    pub price: Balance,
+    pub expire: BlockNumber,
+}
+
+// A value placed in storage that represents the current version of the Market storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, RuntimeDebug)]
+enum Releases {
+    V1_0_0,
+    V2_0_0,
+    V3_0_0,
+    V4_0_0,
+    V5_0_0,
+    V6_0_0,
+    V7_0_0,
+    V8_0_0,
+    V9_0_0,
+    V10_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as Market {
         pub Manager get(fn manager) config(manager): T::AccountId;
-        pub VirginSellPercent get(fn vergin_sell_percent) config(virgin_sell_percent): Percent;
-        pub NormalSellPercent get(fn normal_sell_percent) config(normal_sell_percent): Percent;
+        /// All market calls are rejected while it's set, except the ones only to cancel or refund.
+        pub Paused get(fn paused): bool;
+        /// The blocks a holder must hold a venus before selling it.
+        pub ResaleCooldown get(fn resale_cooldown): T::BlockNumber;
+        /// The cooldowns set by the artists for their own venuses, the stricter one of it and
+        /// `ResaleCooldown` applies.
+        pub ArtistCooldowns get(fn artist_cooldowns): map hasher(twox_64_concat) ArtistId => Option<T::BlockNumber>;
+        pub VirginFeeSchedule get(fn virgin_fee_schedule) config(): FeeSchedule<T::AccountId>;
+        pub NormalFeeSchedule get(fn normal_fee_schedule) config(): FeeSchedule<T::AccountId>;
 
         pub VirginSellOut get(fn virgin_sell_out): map hasher(identity) ArtvenusId<T> => Option<()>;
-        pub OnSell get(fn on_sell): map hasher(identity) ArtvenusId<T> => Option<OnSellInfo<T::Balance, T::BlockNumber>>;
+        pub OnSell get(fn on_sell): map hasher(identity) ArtvenusId<T> => Option<OnSellInfo<T::AccountId, T::Balance, T::BlockNumber>>;
+
+        pub Auctions get(fn auctions): map hasher(identity) ArtvenusId<T> => Option<AuctionInfo<T::AccountId, T::Balance, T::BlockNumber>>;
+        pub AuctionsEndAt get(fn auctions_end_at):
+            double_map hasher(twox_64_concat) T::BlockNumber, hasher(identity) ArtvenusId<T> => Option<()>;
+
+        /// The sell orders expire at the block.
+        pub SellsExpireAt get(fn sells_expire_at): double_map hasher(twox_64_concat) T::BlockNumber, hasher(identity) ArtvenusId<T> => Option<()>;
+        /// The expired sell orders before the block are all removed.
+        pub ExpireCursor get(fn expire_cursor): T::BlockNumber;
+
+        pub Raffles get(fn raffles): map hasher(identity) ArtvenusId<T> => Option<RaffleInfo<T::AccountId, T::Balance, T::BlockNumber>>;
+        pub RafflesEndAt get(fn raffles_end_at): double_map hasher(twox_64_concat) T::BlockNumber, hasher(identity) ArtvenusId<T> => Option<()>;
+
+        pub DutchAuctions get(fn dutch_auctions): map hasher(identity) ArtvenusId<T> => Option<DutchInfo<T::Balance, T::BlockNumber>>;
+
+        pub Offers get(fn offers):
+            double_map hasher(identity) ArtvenusId<T>, hasher(blake2_128_concat) T::AccountId => Option<OfferInfo<T::Balance, T::BlockNumber>>;
+        /// The number of the offers for a venus.
+        pub OfferCounts get(fn offer_counts): map hasher(identity) ArtvenusId<T> => u32;
+
+        pub NextLotId get(fn next_lot_id): LotId;
+        pub Lots get(fn lots): map hasher(twox_64_concat) LotId => Option<LotInfo<T::AccountId, ArtvenusId<T>, T::Balance, T::BlockNumber>>;
+        /// The lot a venus is in.
+        pub LotOf get(fn lot_of): map hasher(identity) ArtvenusId<T> => Option<LotId>;
+
+        pub NextSwapId get(fn next_swap_id): SwapId;
+        pub Swaps get(fn swaps): map hasher(twox_64_concat) SwapId => Option<SwapInfo<T::AccountId, ArtvenusId<T>, T::Balance>>;
+        /// The swaps a venus is given or taken in.
+        pub SwapsOf get(fn swaps_of):
+            double_map hasher(identity) ArtvenusId<T>, hasher(twox_64_concat) SwapId => Option<()>;
+
+        /// The galleries could be consignees.
+        pub Galleries get(fn galleries): map hasher(blake2_128_concat) T::AccountId => Option<()>;
+        pub Consignments get(fn consignments): map hasher(identity) ArtvenusId<T> => Option<ConsignInfo<T::AccountId>>;
+
+        pub Presales get(fn presales): map hasher(twox_64_concat) ArtistId => Option<PresaleInfo<T::AccountId, HashOf<T>, T::BlockNumber>>;
+        /// The accounts proved in the merkle root of a presale phase.
+        pub PresaleMembers get(fn presale_members): double_map hasher(twox_64_concat) ArtistId, hasher(blake2_128_concat) T::AccountId => Option<()>;
+        /// The purchases of an account in the presale phase of an artist.
+        pub PresalePurchases get(fn presale_purchases): double_map hasher(twox_64_concat) ArtistId, hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// The last deal of a venus.
+        pub LastSale get(fn last_sale): map hasher(identity) ArtvenusId<T> => Option<SaleRecord<T::AccountId, T::Balance, T::BlockNumber>>;
+        pub ArtistSales get(fn artist_sales): map hasher(twox_64_concat) ArtistId => SalesStats<T::Balance>;
+
+        /// Storage version of the module.
+        StorageVersion build(|_: &GenesisConfig<T>| Releases::V10_0_0): Releases;
     }
 }
 
 decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        /// The least part for the artist in virgin sell.
+        const MinVirginSellPercent: Percent = T::MinVirginSellPercent::get();
+
+        /// The most part for the artist (royalty) in normal sell.
+        const MaxNormalSellPercent: Percent = T::MaxNormalSellPercent::get();
+
+        /// The most expired sell orders removed in a block.
+        const MaxExpiredSellsPerBlock: u32 = T::MaxExpiredSellsPerBlock::get();
+
+        /// The most entrants in a raffle.
+        const MaxRaffleEntrants: u32 = T::MaxRaffleEntrants::get();
+
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            migration::on_runtime_upgrade::<T>()
+        }
+
+        fn on_initialize(n: T::BlockNumber) -> Weight {
+            // the auctions and raffles ending at `n` are done in `on_finalize`
+            Self::remove_expired_sells(n).saturating_add(Self::endings_weight(n))
+        }
+
+        /// Put a sell order of `venus_id` priced in `currency`, only `buyer` could deal it if
+        /// it's designated. The sell order is removed at the `expire` block if any.
         #[weight=0]
-        pub fn sell(origin, venus_id: ArtvenusId<T>, #[compact] price: T::Balance) -> DispatchResult {
+        pub fn sell(
+            origin,
+            venus_id: ArtvenusId<T>,
+            #[compact] price: T::Balance,
+            currency: SellCurrency,
+            buyer: Option<<T::Lookup as StaticLookup>::Source>,
+            expire: Option<T::BlockNumber>
+        ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            Self::sell_impl(who, venus_id, price)?;
+            Self::ensure_not_paused()?;
+            let buyer = buyer.map(T::Lookup::lookup).transpose()?;
+            Self::sell_impl(who, venus_id, price, currency, buyer, expire)?;
             Ok(())
         }
 
+        /// Buy `venus_id` at the current price, which must not be over `max_price`.
+        ///
+        /// `time` is the `time` of the sell order read by the buyer, the deal fails if the sell
+        /// order is changed since then.
         #[weight=0]
-        pub fn deal(origin, venus_id: ArtvenusId<T>) -> DispatchResult {
+        pub fn deal(
+            origin,
+            venus_id: ArtvenusId<T>,
+            #[compact] max_price: T::Balance,
+            time: T::BlockNumber
+        ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            Self::deal_impl(who, venus_id)?;
+            Self::ensure_not_paused()?;
+            Self::deal_impl(who.clone(), who, venus_id, max_price, time)?;
             Ok(())
         }
-    }
-}
 
-impl<T: Trait> Module<T> {
-    pub fn get_on_sell(
-        venus_id: ArtvenusId<T>,
-    ) -> Result<OnSellInfo<T::Balance, T::BlockNumber>, DispatchError> {
-        let sell = Self::on_sell(venus_id).ok_or(Error::<T>::NotOnSell)?;
-        Ok(sell)
-    }
-}
+        /// Buy `venus_id` for `recipient` as a gift, see `deal`.
+        #[weight=0]
+        pub fn deal_for(
+            origin,
+            venus_id: ArtvenusId<T>,
+            recipient: <T::Lookup as StaticLookup>::Source,
+            #[compact] max_price: T::Balance,
+            time: T::BlockNumber
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            let recipient = T::Lookup::lookup(recipient)?;
+            Self::deal_impl(who, recipient, venus_id, max_price, time)?;
+            Ok(())
+        }
 
-impl<T: Trait> Module<T> {
-    fn sell_impl(who: T::AccountId, venus_id: ArtvenusId<T>, price: T::Balance) -> DispatchResult {
-        let artvenus: Artvenus<T> = cirml_artvenuses::Module::<T>::get_artvenus(venus_id)?;
-        let artist_id = artvenus.origin;
+        #[weight=0]
+        pub fn cancel_sell(origin, venus_id: ArtvenusId<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::cancel_sell_impl(who, venus_id)?;
+            Ok(())
+        }
 
-        if Self::get_on_sell(venus_id).is_ok() {
-            Err(Error::<T>::AlreadyOnSell)?;
+        #[weight=0]
+        pub fn update_price(origin, venus_id: ArtvenusId<T>, #[compact] price: T::Balance) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::update_price_impl(who, venus_id, price)?;
+            Ok(())
         }
-        let state = if Self::virgin_sell_out(&venus_id).is_none() {
-            // artist accountid may be changed, thus must get every time
-            let artist_account = cirml_artists::Module::<T>::get_artist_account(artist_id)?;
-            // virgin sell
-            if who != artist_account {
-                Err(Error::<T>::NotCreaterInVirginSell)?;
+
+        /// Auctions are in `SellCurrency::Native` only, as raffles, offers and swaps are, see
+        /// the `currency` module.
+        #[weight=0]
+        pub fn start_auction(origin, venus_id: ArtvenusId<T>, #[compact] reserve_price: T::Balance, end_block: T::BlockNumber) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::start_auction_impl(who, venus_id, reserve_price, end_block)?;
+            Ok(())
+        }
+
+        #[weight=0]
+        pub fn bid(origin, venus_id: ArtvenusId<T>, #[compact] price: T::Balance) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::bid_impl(who, venus_id, price)?;
+            Ok(())
+        }
+
+        #[weight=0]
+        pub fn start_dutch_auction(
+            origin,
+            venus_id: ArtvenusId<T>,
+            #[compact] start_price: T::Balance,
+            #[compact] floor_price: T::Balance,
+            decay: PriceDecay<T::Balance, T::BlockNumber>,
+            currency: SellCurrency
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::start_dutch_auction_impl(who, venus_id, start_price, floor_price, decay, currency)?;
+            Ok(())
+        }
+
+        /// Sell `venus_id` at `price` to an entrant drawn at the `end` block.
+        #[weight=0]
+        pub fn start_raffle(
+            origin,
+            venus_id: ArtvenusId<T>,
+            #[compact] price: T::Balance,
+            end: T::BlockNumber
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::start_raffle_impl(who, venus_id, price, end)?;
+            Ok(())
+        }
+
+        #[weight=0]
+        pub fn enter_raffle(origin, venus_id: ArtvenusId<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::enter_raffle_impl(who, venus_id)?;
+            Ok(())
+        }
+
+        #[weight=0]
+        pub fn make_offer(origin, venus_id: ArtvenusId<T>, #[compact] price: T::Balance, expire: T::BlockNumber) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::make_offer_impl(who, venus_id, price, expire)?;
+            Ok(())
+        }
+
+        #[weight=0]
+        pub fn withdraw_offer(origin, venus_id: ArtvenusId<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let offer = Self::offers(&venus_id, &who).ok_or(Error::<T>::OfferNotExist)?;
+            Self::cancel_offer(&who, venus_id, offer);
+            Ok(())
+        }
+
+        #[weight=0]
+        pub fn accept_offer(origin, venus_id: ArtvenusId<T>, buyer: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            let buyer = T::Lookup::lookup(buyer)?;
+            Self::accept_offer_impl(who, venus_id, buyer)?;
+            Ok(())
+        }
+
+        /// Put a lot of `venuses` on sell in `currency`, each with its part of the price.
+        #[weight=0]
+        pub fn sell_lot(origin, venuses: Vec<(ArtvenusId<T>, T::Balance)>, currency: SellCurrency) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::sell_lot_impl(who, venuses, currency)?;
+            Ok(())
+        }
+
+        /// Buy all venuses in `lot_id`, see `deal` for `max_price` and `time`.
+        #[weight=0]
+        pub fn deal_lot(
+            origin,
+            #[compact] lot_id: LotId,
+            #[compact] max_price: T::Balance,
+            time: T::BlockNumber
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::deal_lot_impl(who, lot_id, max_price, time)?;
+            Ok(())
+        }
+
+        #[weight=0]
+        pub fn cancel_lot(origin, #[compact] lot_id: LotId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::cancel_lot_impl(who, lot_id)?;
+            Ok(())
+        }
+
+        /// Propose to swap `give` of the caller and `top_up` for `take`, `valuation` is the
+        /// declared value of `give`.
+        #[weight=0]
+        pub fn propose_swap(
+            origin,
+            give: ArtvenusId<T>,
+            take: ArtvenusId<T>,
+            #[compact] top_up: T::Balance,
+            #[compact] valuation: T::Balance
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::propose_swap_impl(who, give, take, top_up, valuation)?;
+            Ok(())
+        }
+
+        /// Accept `swap_id` by the holder of the venus to take.
+        #[weight=0]
+        pub fn accept_swap(origin, #[compact] swap_id: SwapId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            Self::accept_swap_impl(who, swap_id)?;
+            Ok(())
+        }
+
+        #[weight=0]
+        pub fn cancel_swap(origin, #[compact] swap_id: SwapId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let swap = Self::swaps(swap_id).ok_or(Error::<T>::SwapNotExist)?;
+            if swap.proposer != who {
+                Err(Error::<T>::NotSeller)?;
             }
-            OnSellState::VirginSell
-        } else {
-            let seller = cirml_artvenuses::Module::<T>::holder_for(venus_id)?;
-            if seller != who {
-                Err(Error::<T>::NotHolderInSell)?;
+            Self::cancel_swap_impl(swap_id, swap);
+            Ok(())
+        }
+
+        #[weight=0]
+        pub fn set_fee_schedule(origin, is_virgin_sell: bool, schedule: FeeSchedule<T::AccountId>) -> DispatchResult {
+            ensure_root(origin)?;
+            let total = match schedule.total() {
+                Some(total) if schedule.is_valid() => total,
+                _ => Err(Error::<T>::InvalidFeeSchedule)?,
+            };
+            if is_virgin_sell {
+                // the artist is the seller, thus receives the royalty and what is left by others
+                let artist = 100 - total.deconstruct() + schedule.royalty.deconstruct();
+                if Percent::from_percent(artist) < T::MinVirginSellPercent::get() {
+                    Err(Error::<T>::PercentOutOfBounds)?;
+                }
+                VirginFeeSchedule::<T>::put(schedule);
+            } else {
+                if schedule.royalty > T::MaxNormalSellPercent::get() {
+                    Err(Error::<T>::PercentOutOfBounds)?;
+                }
+                NormalFeeSchedule::<T>::put(schedule);
             }
-            OnSellState::Sell
-        };
+            Self::deposit_event(RawEvent::FeeScheduleUpdate(is_virgin_sell));
+            Ok(())
+        }
 
-        // put sell order
-        let sell = OnSellInfo {
-            state,
-            price,
-            time: system::Module::<T>::block_number(),
-        };
-        OnSell::<T>::insert(&venus_id, sell);
+        /// Register `gallery` (true) or unregister it (false), by the manager or root.
+        #[weight=0]
+        pub fn set_gallery(origin, gallery: <T::Lookup as StaticLookup>::Source, registered: bool) -> DispatchResult {
+            Self::ensure_manager_or_root(origin)?;
+            let gallery = T::Lookup::lookup(gallery)?;
 
-        Self::deposit_event(RawEvent::OnSell(who, venus_id, price));
-        Ok(())
-    }
+            if registered {
+                Galleries::<T>::insert(&gallery, ());
+            } else {
+                Galleries::<T>::remove(&gallery);
+            }
+            Self::deposit_event(RawEvent::GalleryUpdate(gallery, registered));
+            Ok(())
+        }
 
-    fn deal_impl(buyer: T::AccountId, venus_id: ArtvenusId<T>) -> DispatchResult {
-        let sell_info = Self::get_on_sell(venus_id)?;
-        let venus_info = cirml_artvenuses::Module::<T>::get_artvenus(venus_id)?;
-        let artist = cirml_artists::Module::<T>::get_artist_account(venus_info.origin)?;
+        /// Authorize the gallery `consignee` to sell and cancel the sell of `venus_id` for the
+        /// `commission`, by the holder.
+        #[weight=0]
+        pub fn consign(
+            origin,
+            venus_id: ArtvenusId<T>,
+            consignee: <T::Lookup as StaticLookup>::Source,
+            commission: Percent
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            let consignee = T::Lookup::lookup(consignee)?;
+            Self::consign_impl(who, venus_id, consignee, commission)?;
+            Ok(())
+        }
 
-        let price = sell_info.price;
-        let free = cirml_balances::Module::<T>::free_balance(&buyer);
-        if free < price {
-            Err(Error::<T>::CantPay)?;
+        /// Revoke the consignment of `venus_id`, the sell order is cancelled if any.
+        #[weight=0]
+        pub fn revoke_consign(origin, venus_id: ArtvenusId<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::revoke_consign_impl(who, venus_id)?;
+            Ok(())
         }
 
-        let is_virgin_sell = if Self::virgin_sell_out(&venus_id).is_none() {
-            let manager = Self::manager();
-            // virgin sell
-            let for_artist_percent = Self::vergin_sell_percent();
-            let for_artist = for_artist_percent.saturating_reciprocal_mul(price);
-            let for_manager = price - for_artist;
-            <cirml_balances::Module<T> as Currency<_>>::transfer(
-                &buyer, &artist, for_artist, KeepAlive,
-            )?;
-            <cirml_balances::Module<T> as Currency<_>>::transfer(
-                &buyer,
-                &manager,
-                for_manager,
-                KeepAlive,
-            )?;
-            // set virgin sell finish
-            VirginSellOut::<T>::insert(&venus_id, ());
-            true
-        } else {
-            let seller = cirml_artvenuses::Module::<T>::holder_for(venus_id)?;
-            // normal sell
-            let for_artist_percent = Self::normal_sell_percent();
-            let for_artist = for_artist_percent.saturating_reciprocal_mul(price);
-            let for_seller = price - for_artist;
-            <cirml_balances::Module<T> as Currency<_>>::transfer(
-                &buyer, &artist, for_artist, KeepAlive,
-            )?;
-            <cirml_balances::Module<T> as Currency<_>>::transfer(
-                &buyer, &seller, for_seller, KeepAlive,
-            )?;
-            false
-        };
-        cirml_artvenuses::Module::<T>::move_artvenus(venus_id, &buyer)
-            .expect("move_artvenus must success");
-        OnSell::<T>::remove(&venus_id);
+        /// Set the presale phase of the venuses of the caller artist, it replaces the former
+        /// phase and its purchases.
+        #[weight=0]
+        pub fn set_presale(
+            origin,
+            allowlist: Allowlist<T::AccountId, HashOf<T>>,
+            start: T::BlockNumber,
+            end: T::BlockNumber,
+            max_purchases: u32
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            let artist_id = cirml_artists::Module::<T>::get_artist_id(&who)?;
+            if end <= start || end <= system::Module::<T>::block_number() || max_purchases == 0 {
+                Err(Error::<T>::InvalidPresale)?;
+            }
 
-        Self::deposit_event(RawEvent::Deal(buyer, venus_id, is_virgin_sell));
-        Ok(())
-    }
-}
+            Self::clear_presale_of(artist_id);
+            Presales::<T>::insert(artist_id, PresaleInfo { allowlist, start, end, max_purchases });
+            Self::deposit_event(RawEvent::PresaleUpdate(artist_id, start, end));
+            Ok(())
+        }
 
-// for runtime-api
-impl<T: Trait> Module<T> {
-    pub fn on_sell_list() -> Vec<(ArtvenusId<T>, OnSellInfo<T::Balance, T::BlockNumber>)> {
-        OnSell::<T>::iter().collect()
+        #[weight=0]
+        pub fn clear_presale(origin) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            let artist_id = cirml_artists::Module::<T>::get_artist_id(&who)?;
+
+            Self::clear_presale_of(artist_id);
+            Self::deposit_event(RawEvent::PresaleClear(artist_id));
+            Ok(())
+        }
+
+        /// Set a resale cooldown for the venuses of the caller artist, stricter than the one of
+        /// the market, or remove it (none).
+        #[weight=0]
+        pub fn set_artist_cooldown(origin, cooldown: Option<T::BlockNumber>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            let artist_id = cirml_artists::Module::<T>::get_artist_id(&who)?;
+
+            ArtistCooldowns::<T>::mutate_exists(artist_id, |c| *c = cooldown);
+            Self::deposit_event(RawEvent::ArtistCooldownUpdate(artist_id, cooldown));
+            Ok(())
+        }
+
+        /// Prove the caller is allowed by the merkle root of the presale phase of `artist_id`.
+        #[weight=0]
+        pub fn join_presale(origin, artist_id: ArtistId, proof: Vec<HashOf<T>>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::ensure_not_paused()?;
+            let presale = Self::presales(artist_id).ok_or(Error::<T>::NoMerklePresale)?;
+            let root = match presale.allowlist {
+                Allowlist::MerkleRoot(root) => root,
+                Allowlist::List(_) => Err(Error::<T>::NoMerklePresale)?,
+            };
+            if !Self::verify_merkle_proof(&who, &proof, &root) {
+                Err(Error::<T>::InvalidMerkleProof)?;
+            }
+
+            PresaleMembers::<T>::insert(artist_id, &who, ());
+            Self::deposit_event(RawEvent::PresaleJoin(who, artist_id, root));
+            Ok(())
+        }
+
+        /// Set the resale cooldown in blocks, by the manager or root.
+        #[weight=0]
+        pub fn set_resale_cooldown(origin, cooldown: T::BlockNumber) -> DispatchResult {
+            Self::ensure_manager_or_root(origin)?;
+
+            ResaleCooldown::<T>::put(cooldown);
+            Self::deposit_event(RawEvent::ResaleCooldownUpdate(cooldown));
+            Ok(())
+        }
+
+        /// Pause or resume the market, by the manager or root.
+        #[weight=0]
+        pub fn set_paused(origin, paused: bool) -> DispatchResult {
+            Self::ensure_manager_or_root(origin)?;
+
+            Paused::put(paused);
+            Self::deposit_event(RawEvent::PauseUpdate(paused));
+            Ok(())
+        }
+
+        #[weight=0]
+        pub fn set_manager(origin, manager: <T::Lookup as StaticLookup>::Source) -> DispatchResult {
+            ensure_root(origin)?;
+            let manager = T::Lookup::lookup(manager)?;
+
+            Manager::<T>::put(&manager);
+            Self::deposit_event(RawEvent::ManagerUpdate(manager));
+            Ok(())
+        }
+
+        /// Set the part for the artist in virgin sell, the platform takes the rest besides the
+        /// extra beneficiaries.
+        #[weight=0]
+        pub fn set_virgin_sell_percent(origin, percent: Percent) -> DispatchResult {
+            ensure_root(origin)?;
+            if percent < T::MinVirginSellPercent::get() {
+                Err(Error::<T>::PercentOutOfBounds)?;
+            }
+
+            let mut schedule = Self::virgin_fee_schedule();
+            // the artist receives the royalty and the rest as the seller, thus only the platform
+            // share decides the part for the artist
+            let others = schedule
+                .beneficiaries
+                .iter()
+                .fold(percent.deconstruct() as u32, |acc, (_, share)| acc + share.deconstruct() as u32);
+            if others > 100 {
+                Err(Error::<T>::InvalidFeeSchedule)?;
+            }
+            schedule.platform = Percent::from_parts(100 - others as u8);
+            schedule.royalty = Percent::from_percent(0);
+            VirginFeeSchedule::<T>::put(schedule);
+
+            Self::deposit_event(RawEvent::VirginSellPercentUpdate(percent));
+            Ok(())
+        }
+
+        /// Set the part for the artist (royalty) in normal sell.
+        #[weight=0]
+        pub fn set_normal_sell_percent(origin, percent: Percent) -> DispatchResult {
+            ensure_root(origin)?;
+            if percent > T::MaxNormalSellPercent::get() {
+                Err(Error::<T>::PercentOutOfBounds)?;
+            }
+
+            let mut schedule = Self::normal_fee_schedule();
+            schedule.royalty = percent;
+            if !schedule.is_valid() {
+                Err(Error::<T>::InvalidFeeSchedule)?;
+            }
+            NormalFeeSchedule::<T>::put(schedule);
+
+            Self::deposit_event(RawEvent::NormalSellPercentUpdate(percent));
+            Ok(())
+        }
+
+        fn on_finalize(n: T::BlockNumber) {
+            let ended: Vec<ArtvenusId<T>> = AuctionsEndAt::<T>::iter_prefix(n).map(|(id, _)| id).collect();
+            for venus_id in ended {
+                Self::settle_auction(venus_id);
+            }
+            AuctionsEndAt::<T>::remove_prefix(n);
+
+            let ended: Vec<ArtvenusId<T>> = RafflesEndAt::<T>::iter_prefix(n).map(|(id, _)| id).collect();
+            for venus_id in ended {
+                Self::draw_raffle(venus_id);
+            }
+            RafflesEndAt::<T>::remove_prefix(n);
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    fn ensure_not_paused() -> DispatchResult {
+        if Self::paused() {
+            Err(Error::<T>::MarketPaused)?;
+        }
+        Ok(())
+    }
+
+    fn ensure_manager_or_root(origin: T::Origin) -> DispatchResult {
+        match origin.into() {
+            Ok(system::RawOrigin::Root) => Ok(()),
+            Ok(system::RawOrigin::Signed(ref who)) if *who == Self::manager() => Ok(()),
+            _ => Err(BadOrigin.into()),
+        }
+    }
+
+    pub fn get_on_sell(
+        venus_id: ArtvenusId<T>,
+    ) -> Result<OnSellInfo<T::AccountId, T::Balance, T::BlockNumber>, DispatchError> {
+        let sell = Self::on_sell(venus_id).ok_or(Error::<T>::NotOnSell)?;
+        Ok(sell)
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Check `who` could put `venus_id` on sell, return the state for the sell order.
+    fn ensure_seller(
+        who: &T::AccountId,
+        venus_id: ArtvenusId<T>,
+    ) -> Result<OnSellState, DispatchError> {
+        let who = &Self::acting_for(who, venus_id);
+        let artvenus: Artvenus<T> = cirml_artvenuses::Module::<T>::get_artvenus(venus_id)?;
+        let artist_id = artvenus.origin;
+
+        if Self::get_on_sell(venus_id).is_ok() {
+            Err(Error::<T>::AlreadyOnSell)?;
+        }
+        if LotOf::<T>::contains_key(&venus_id) {
+            Err(Error::<T>::InLot)?;
+        }
+        let state = if Self::virgin_sell_out(&venus_id).is_none() {
+            // artist accountid may be changed, thus must get every time
+            let artist_account = cirml_artists::Module::<T>::get_artist_account(artist_id)?;
+            // virgin sell
+            if *who != artist_account {
+                Err(Error::<T>::NotCreaterInVirginSell)?;
+            }
+            OnSellState::VirginSell
+        } else {
+            let seller = cirml_artvenuses::Module::<T>::holder_for(venus_id)?;
+            if seller != *who {
+                Err(Error::<T>::NotHolderInSell)?;
+            }
+            if Self::in_cooldown(venus_id, artist_id) {
+                Err(Error::<T>::InCooldown)?;
+            }
+            OnSellState::Sell
+        };
+        Ok(state)
+    }
+
+    /// Ensure the holder of `venus_id` could resell it, there is no cooldown in virgin sell.
+    fn ensure_not_in_cooldown(venus_id: ArtvenusId<T>) -> DispatchResult {
+        if Self::virgin_sell_out(&venus_id).is_none() {
+            return Ok(());
+        }
+        let artvenus: Artvenus<T> = cirml_artvenuses::Module::<T>::get_artvenus(venus_id)?;
+        if Self::in_cooldown(venus_id, artvenus.origin) {
+            Err(Error::<T>::InCooldown)?;
+        }
+        Ok(())
+    }
+
+    /// The venus is acquired by the holder within the resale cooldown.
+    fn in_cooldown(venus_id: ArtvenusId<T>, artist_id: ArtistId) -> bool {
+        let acquired_at = match cirml_artvenuses::Module::<T>::acquired_at(&venus_id) {
+            Some(acquired_at) => acquired_at,
+            None => return false,
+        };
+        let cooldown = Self::artist_cooldowns(artist_id)
+            .map_or(Self::resale_cooldown(), |c| c.max(Self::resale_cooldown()));
+        system::Module::<T>::block_number() < acquired_at.saturating_add(cooldown)
+    }
+
+    fn sell_impl(
+        who: T::AccountId,
+        venus_id: ArtvenusId<T>,
+        price: T::Balance,
+        currency: SellCurrency,
+        buyer: Option<T::AccountId>,
+        expire: Option<T::BlockNumber>,
+    ) -> DispatchResult {
+        let state = Self::ensure_seller(&who, venus_id)?;
+        let now = system::Module::<T>::block_number();
+        if expire.map_or(false, |expire| expire <= now) {
+            Err(Error::<T>::InvalidExpire)?;
+        }
+        Self::ensure_receivers_exist(venus_id, price, currency, &who)?;
+
+        // put sell order
+        let sell = OnSellInfo {
+            state,
+            price,
+            time: now,
+            buyer,
+            expire,
+            currency,
+            lister: who.clone(),
+        };
+        OnSell::<T>::insert(&venus_id, sell);
+        if let Some(expire) = expire {
+            SellsExpireAt::<T>::insert(expire, &venus_id, ());
+        }
+
+        Self::deposit_event(RawEvent::OnSell(who, venus_id, price));
+        Ok(())
+    }
+
+    /// Return the sell order of `venus_id` if `who` could change it, auctions are excluded.
+    fn ensure_sell_owner(
+        who: &T::AccountId,
+        venus_id: ArtvenusId<T>,
+    ) -> Result<OnSellInfo<T::AccountId, T::Balance, T::BlockNumber>, DispatchError> {
+        let sell_info = Self::get_on_sell(venus_id)?;
+        if sell_info.state.is_reserving() {
+            Err(Error::<T>::InBidding)?;
+        }
+        if !Self::is_seller(&Self::acting_for(who, venus_id), venus_id)? {
+            Err(Error::<T>::NotSeller)?;
+        }
+        Ok(sell_info)
+    }
+
+    fn cancel_sell_impl(who: T::AccountId, venus_id: ArtvenusId<T>) -> DispatchResult {
+        let _ = Self::ensure_sell_owner(&who, venus_id)?;
+        Self::remove_on_sell(venus_id);
+
+        Self::deposit_event(RawEvent::CancelSell(who, venus_id));
+        Ok(())
+    }
+
+    fn update_price_impl(
+        who: T::AccountId,
+        venus_id: ArtvenusId<T>,
+        price: T::Balance,
+    ) -> DispatchResult {
+        let mut sell_info = Self::ensure_sell_owner(&who, venus_id)?;
+        Self::ensure_receivers_exist(venus_id, price, sell_info.currency, &sell_info.lister)?;
+        if let Some(dutch) = Self::dutch_auctions(&venus_id) {
+            // restart the dutch auction from the new price
+            if dutch.floor > price {
+                Err(Error::<T>::InvalidDutchAuction)?;
+            }
+        }
+        sell_info.price = price;
+        sell_info.time = system::Module::<T>::block_number();
+        OnSell::<T>::insert(&venus_id, sell_info);
+
+        Self::deposit_event(RawEvent::UpdatePrice(who, venus_id, price));
+        Ok(())
+    }
+
+    /// Quote a deal of `venus_id` at `price` with the current fee schedule, the commission is
+    /// charged only if `lister`, who puts or accepts the deal, is the consignee.
+    pub fn quote(
+        venus_id: ArtvenusId<T>,
+        price: T::Balance,
+        lister: &T::AccountId,
+    ) -> Result<DealQuote<T::AccountId, T::Balance>, DispatchError> {
+        let venus_info = cirml_artvenuses::Module::<T>::get_artvenus(venus_id)?;
+        let artist = cirml_artists::Module::<T>::get_artist_account(venus_info.origin)?;
+
+        let is_virgin_sell = Self::virgin_sell_out(&venus_id).is_none();
+        let (schedule, seller) = if is_virgin_sell {
+            (Self::virgin_fee_schedule(), artist.clone())
+        } else {
+            let seller = cirml_artvenuses::Module::<T>::holder_for(venus_id)?;
+            (Self::normal_fee_schedule(), seller)
+        };
+        let quote = DealQuote::new(
+            price,
+            is_virgin_sell,
+            schedule,
+            Self::manager(),
+            artist,
+            seller,
+            Self::commission_of(venus_id, lister),
+        )
+        .ok_or(Error::<T>::InvalidFeeSchedule)?;
+        Ok(quote)
+    }
+
+    /// `buyer` pays for `venus_id` and `recipient` receives it.
+    fn deal_impl(
+        buyer: T::AccountId,
+        recipient: T::AccountId,
+        venus_id: ArtvenusId<T>,
+        max_price: T::Balance,
+        time: T::BlockNumber,
+    ) -> DispatchResult {
+        let sell_info = Self::get_on_sell(venus_id)?;
+        if sell_info.state.is_reserving() {
+            Err(Error::<T>::InBidding)?;
+        }
+        if sell_info.time != time {
+            Err(Error::<T>::SellChanged)?;
+        }
+        if sell_info.buyer.as_ref().map_or(false, |b| *b != buyer) {
+            Err(Error::<T>::NotDesignatedBuyer)?;
+        }
+        if Self::is_seller(&buyer, venus_id)?
+            || cirml_artvenuses::Module::<T>::is_holder(venus_id, &buyer)?
+        {
+            Err(Error::<T>::DealOwnVenus)?;
+        }
+        if Self::is_expired(&sell_info) {
+            Err(Error::<T>::SellExpired)?;
+        }
+
+        let price = Self::current_price(venus_id, &sell_info);
+        if price > max_price {
+            Err(Error::<T>::PriceTooHigh)?;
+        }
+        let is_virgin_sell = sell_info.state == OnSellState::VirginSell;
+        let presale = Self::ensure_presale_buyer(&buyer, venus_id, is_virgin_sell, 0)?;
+        Self::settle_all(
+            &buyer,
+            &recipient,
+            &sell_info.lister,
+            &[(venus_id, price)],
+            sell_info.currency,
+            false,
+        )?;
+        if let Some(artist_id) = presale {
+            PresalePurchases::<T>::mutate(artist_id, &buyer, |n| *n = n.saturating_add(1));
+        }
+        Ok(())
+    }
+}
+
+// presale
+impl<T: Trait> Module<T> {
+    /// Check `buyer` could deal the virgin sell of `venus_id` in the presale phase of the artist,
+    /// with `pending` purchases of the phase in the same deal. Others could not deal from the time
+    /// the phase is set until its end, and the allowlist could deal since its start. The artist
+    /// is returned if the deal is counted in the phase.
+    fn ensure_presale_buyer(
+        buyer: &T::AccountId,
+        venus_id: ArtvenusId<T>,
+        is_virgin_sell: bool,
+        pending: u32,
+    ) -> Result<Option<ArtistId>, DispatchError> {
+        if !is_virgin_sell {
+            return Ok(None);
+        }
+        let artist_id = cirml_artvenuses::Module::<T>::get_artvenus(venus_id)?.origin;
+        let presale = match Self::presales(artist_id) {
+            Some(presale) => presale,
+            None => return Ok(None),
+        };
+        let now = system::Module::<T>::block_number();
+        if now >= presale.end {
+            return Ok(None);
+        }
+
+        let allowed = match presale.allowlist {
+            Allowlist::List(accounts) => accounts.contains(buyer),
+            Allowlist::MerkleRoot(_) => PresaleMembers::<T>::contains_key(artist_id, buyer),
+        };
+        if !allowed {
+            Err(Error::<T>::NotInAllowlist)?;
+        }
+        if now < presale.start {
+            Err(Error::<T>::PresaleNotStarted)?;
+        }
+        if Self::presale_purchases(artist_id, buyer).saturating_add(pending)
+            >= presale.max_purchases
+        {
+            Err(Error::<T>::PresaleLimitReached)?;
+        }
+        Ok(Some(artist_id))
+    }
+
+    fn clear_presale_of(artist_id: ArtistId) {
+        Presales::<T>::remove(artist_id);
+        PresaleMembers::<T>::remove_prefix(artist_id);
+        PresalePurchases::<T>::remove_prefix(artist_id);
+    }
+
+    /// The leaf of `who` is hashed with every node of `proof` in turn, the smaller one first.
+    fn verify_merkle_proof(who: &T::AccountId, proof: &[HashOf<T>], root: &HashOf<T>) -> bool {
+        let leaf = T::Hashing::hash_of(who);
+        let computed = proof.iter().fold(leaf, |node, sibling| {
+            if node <= *sibling {
+                T::Hashing::hash_of(&(node, sibling))
+            } else {
+                T::Hashing::hash_of(&(sibling, node))
+            }
+        });
+        computed == *root
+    }
+}
+
+// settlement
+impl<T: Trait> Module<T> {
+    /// Settle a deal of `venus_id` at `price` in `currency` for `buyer`.
+    ///
+    /// The price is reserved from `buyer` at first (unless `reserved` is set, e.g. for the price
+    /// of a winning bid), then repatriated to every receiver, at last the venus is moved to
+    /// `buyer`. All checks are done before any balance or storage is touched, thus a deal is
+    /// either finished entirely or failed with nothing changed.
+    fn settle(
+        buyer: &T::AccountId,
+        lister: &T::AccountId,
+        venus_id: ArtvenusId<T>,
+        price: T::Balance,
+        currency: SellCurrency,
+        reserved: bool,
+    ) -> DispatchResult {
+        Self::settle_all(
+            buyer,
+            buyer,
+            lister,
+            &[(venus_id, price)],
+            currency,
+            reserved,
+        )
+    }
+
+    /// Settle the deals of several venuses at their prices together, see `settle`. `buyer` pays
+    /// and the venuses are moved to `recipient`.
+    fn settle_all(
+        buyer: &T::AccountId,
+        recipient: &T::AccountId,
+        lister: &T::AccountId,
+        venuses: &[(ArtvenusId<T>, T::Balance)],
+        currency: SellCurrency,
+        reserved: bool,
+    ) -> DispatchResult {
+        let mut total = T::Balance::zero();
+        let mut quotes = Vec::with_capacity(venuses.len());
+        // the receivers of all deals, each receives once
+        let mut receivers: Vec<(T::AccountId, T::Balance)> = vec![];
+        for (venus_id, price) in venuses.iter() {
+            let quote = Self::quote(*venus_id, *price, lister)?;
+            total = total.checked_add(price).ok_or(Error::<T>::Overflow)?;
+            for (receiver, value) in quote.receivers() {
+                match receivers.iter_mut().find(|(who, _)| *who == receiver) {
+                    Some((_, sum)) => {
+                        *sum = sum.checked_add(&value).ok_or(Error::<T>::Overflow)?;
+                    }
+                    None => receivers.push((receiver, value)),
+                }
+            }
+            let artist_id = cirml_artvenuses::Module::<T>::get_artvenus(*venus_id)?.origin;
+            let record = SaleRecord {
+                price: *price,
+                currency,
+                block: system::Module::<T>::block_number(),
+                buyer: buyer.clone(),
+                seller: quote.seller.0,
+                is_virgin_sell: quote.is_virgin_sell,
+            };
+            quotes.push((*venus_id, artist_id, record));
+        }
+        Self::ensure_can_settle(buyer, venuses, total, currency, reserved, &receivers)?;
+
+        if !reserved {
+            Self::reserve_in(currency, buyer, total).map_err(|_| Error::<T>::CantPay)?;
+        }
+        Self::repatriate_all(currency, buyer, &receivers)?;
+        for (venus_id, artist_id, record) in quotes {
+            let is_virgin_sell = record.is_virgin_sell;
+            if is_virgin_sell {
+                // set virgin sell finish
+                VirginSellOut::<T>::insert(&venus_id, ());
+            }
+            let acquisition = if buyer == recipient {
+                AcquisitionType::Sold
+            } else {
+                AcquisitionType::Gifted
+            };
+            cirml_artvenuses::Module::<T>::move_artvenus(
+                venus_id,
+                recipient,
+                acquisition,
+                Some((record.price, record.currency)),
+            )?;
+            Self::remove_on_sell(venus_id);
+            Consignments::<T>::remove(&venus_id);
+            Self::record_sale(venus_id, artist_id, record);
+
+            Self::deposit_event(RawEvent::Deal(
+                buyer.clone(),
+                recipient.clone(),
+                venus_id,
+                is_virgin_sell,
+            ));
+        }
+        Ok(())
+    }
+
+    fn record_sale(
+        venus_id: ArtvenusId<T>,
+        artist_id: ArtistId,
+        record: SaleRecord<T::AccountId, T::Balance, T::BlockNumber>,
+    ) {
+        ArtistSales::<T>::mutate(artist_id, |stats| {
+            match record.currency {
+                SellCurrency::Native => stats.volume = stats.volume.saturating_add(record.price),
+                SellCurrency::Stable => {
+                    stats.stable_volume = stats.stable_volume.saturating_add(record.price)
+                }
+            }
+            stats.count = stats.count.saturating_add(1);
+        });
+        LastSale::<T>::insert(&venus_id, record);
+    }
+
+    /// Repatriate the reserved balance of `payer` in `currency` to every receiver.
+    fn repatriate_all(
+        currency: SellCurrency,
+        payer: &T::AccountId,
+        receivers: &[(T::AccountId, T::Balance)],
+    ) -> DispatchResult {
+        for (receiver, value) in receivers.iter() {
+            Self::repatriate_reserved_in(currency, payer, receiver, *value)?;
+        }
+        Ok(())
+    }
+
+    /// Ensure every receiver of a deal of `venus_id` at `price` listed by `lister` holds
+    /// `currency`, otherwise the sell order could never be dealt.
+    fn ensure_receivers_exist(
+        venus_id: ArtvenusId<T>,
+        price: T::Balance,
+        currency: SellCurrency,
+        lister: &T::AccountId,
+    ) -> DispatchResult {
+        let quote = Self::quote(venus_id, price, lister)?;
+        for (receiver, value) in quote.receivers() {
+            if !value.is_zero() && Self::total_balance_in(currency, &receiver).is_zero() {
+                Err(Error::<T>::ReceiverNotExist)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check every step of `settle_all` could be done.
+    fn ensure_can_settle(
+        buyer: &T::AccountId,
+        venuses: &[(ArtvenusId<T>, T::Balance)],
+        price: T::Balance,
+        currency: SellCurrency,
+        reserved: bool,
+        receivers: &[(T::AccountId, T::Balance)],
+    ) -> DispatchResult {
+        // the venuses could be moved
+        for (venus_id, _) in venuses.iter() {
+            let _ = cirml_artvenuses::Module::<T>::holder_for(*venus_id)?;
+        }
+        // the buyer could pay
+        if reserved {
+            if Self::reserved_balance_in(currency, buyer) < price {
+                Err(Error::<T>::CantPay)?;
+            }
+        } else if !Self::can_reserve_in(currency, buyer, price) {
+            Err(Error::<T>::CantPay)?;
+        }
+        // every receiver could accept the repatriation
+        for (receiver, value) in receivers.iter() {
+            if value.is_zero() || receiver == buyer {
+                continue;
+            }
+            if Self::total_balance_in(currency, receiver).is_zero() {
+                Err(Error::<T>::ReceiverNotExist)?;
+            }
+            if Self::free_balance_in(currency, receiver)
+                .checked_add(value)
+                .is_none()
+            {
+                Err(Error::<T>::Overflow)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Remove the sell order of `venus_id`, with its dutch auction if any.
+    fn remove_on_sell(venus_id: ArtvenusId<T>) {
+        if let Some(OnSellInfo {
+            expire: Some(expire),
+            ..
+        }) = OnSell::<T>::take(&venus_id)
+        {
+            SellsExpireAt::<T>::remove(expire, &venus_id);
+        }
+        DutchAuctions::<T>::remove(&venus_id);
+    }
+
+    /// The sell order is expired but may not be removed yet.
+    pub fn is_expired(sell_info: &OnSellInfo<T::AccountId, T::Balance, T::BlockNumber>) -> bool {
+        sell_info.expire.map_or(false, |expire| {
+            expire <= system::Module::<T>::block_number()
+        })
+    }
+
+    /// Remove the sell orders expired at or before `now`, at most `MaxExpiredSellsPerBlock` of
+    /// them, the rest are left to the following blocks.
+    fn remove_expired_sells(now: T::BlockNumber) -> Weight {
+        let limit = T::MaxExpiredSellsPerBlock::get() as usize;
+        let mut cursor = Self::expire_cursor();
+        let mut removed = 0;
+        let mut reads = 1;
+        while cursor <= now && removed < limit {
+            let expired: Vec<ArtvenusId<T>> = SellsExpireAt::<T>::iter_prefix(cursor)
+                .map(|(id, _)| id)
+                .take(limit - removed)
+                .collect();
+            reads += expired.len() as Weight + 1;
+            for venus_id in expired {
+                Self::remove_on_sell(venus_id);
+                Self::deposit_event(RawEvent::SellExpire(venus_id));
+                removed += 1;
+            }
+            if SellsExpireAt::<T>::iter_prefix(cursor).next().is_some() {
+                break;
+            }
+            cursor = cursor.saturating_add(One::one());
+        }
+        ExpireCursor::<T>::put(cursor);
+        T::DbWeight::get().reads_writes(reads, removed as Weight * 3 + 1)
+    }
+
+    /// The worst case weight of `on_finalize` at `n`: every auction or raffle ending at `n` is
+    /// settled, and every bidder and entrant is refunded.
+    fn endings_weight(n: T::BlockNumber) -> Weight {
+        let mut reads = 0;
+        let mut settlements = 0;
+        let mut refunds = 0;
+        for _ in AuctionsEndAt::<T>::iter_prefix(n) {
+            reads += 1;
+            settlements += 1;
+            refunds += 1;
+        }
+        for (venus_id, _) in RafflesEndAt::<T>::iter_prefix(n) {
+            reads += 2;
+            settlements += 1;
+            refunds += Self::raffles(&venus_id).map_or(0, |raffle| raffle.entrants.len() as Weight);
+        }
+        let settled = settlements * SETTLE_READS_WRITES;
+        T::DbWeight::get().reads_writes(reads + settled + refunds, settled + refunds + 2)
+    }
+
+    /// The price to deal `venus_id` at the current block, it decreases for a dutch auction.
+    pub fn current_price(
+        venus_id: ArtvenusId<T>,
+        sell_info: &OnSellInfo<T::AccountId, T::Balance, T::BlockNumber>,
+    ) -> T::Balance {
+        let dutch = match Self::dutch_auctions(&venus_id) {
+            Some(dutch) => dutch,
+            None => return sell_info.price,
+        };
+        let elapsed = system::Module::<T>::block_number().saturating_sub(sell_info.time);
+        let decrease = match dutch.decay {
+            PriceDecay::Linear(amount) => {
+                amount.saturating_mul(elapsed.saturated_into::<u64>().saturated_into())
+            }
+            PriceDecay::Step(blocks, amount) => {
+                let steps = elapsed / blocks.max(One::one());
+                amount.saturating_mul(steps.saturated_into::<u64>().saturated_into())
+            }
+        };
+        sell_info.price.saturating_sub(decrease).max(dutch.floor)
+    }
+}
+
+// dutch auction
+impl<T: Trait> Module<T> {
+    fn start_dutch_auction_impl(
+        who: T::AccountId,
+        venus_id: ArtvenusId<T>,
+        start_price: T::Balance,
+        floor_price: T::Balance,
+        decay: PriceDecay<T::Balance, T::BlockNumber>,
+        currency: SellCurrency,
+    ) -> DispatchResult {
+        let state = Self::ensure_seller(&who, venus_id)?;
+        if floor_price > start_price {
+            Err(Error::<T>::InvalidDutchAuction)?;
+        }
+        if let PriceDecay::Step(blocks, _) = &decay {
+            if blocks.is_zero() {
+                Err(Error::<T>::InvalidDutchAuction)?;
+            }
+        }
+        Self::ensure_receivers_exist(venus_id, start_price, currency, &who)?;
+
+        let sell = OnSellInfo {
+            state,
+            price: start_price,
+            time: system::Module::<T>::block_number(),
+            buyer: None,
+            expire: None,
+            currency,
+            lister: who.clone(),
+        };
+        OnSell::<T>::insert(&venus_id, sell);
+        DutchAuctions::<T>::insert(
+            &venus_id,
+            DutchInfo {
+                floor: floor_price,
+                decay,
+            },
+        );
+
+        Self::deposit_event(RawEvent::DutchAuctionStart(
+            who,
+            venus_id,
+            start_price,
+            floor_price,
+        ));
+        Ok(())
+    }
+}
+
+// lot
+impl<T: Trait> Module<T> {
+    fn sell_lot_impl(
+        who: T::AccountId,
+        venuses: Vec<(ArtvenusId<T>, T::Balance)>,
+        currency: SellCurrency,
+    ) -> DispatchResult {
+        if venuses.is_empty() || venuses.len() > MAX_LOT_VENUSES {
+            Err(Error::<T>::InvalidLot)?;
+        }
+        let mut price = T::Balance::zero();
+        for (i, (venus_id, part)) in venuses.iter().enumerate() {
+            if venuses[..i].iter().any(|(id, _)| id == venus_id) {
+                Err(Error::<T>::InvalidLot)?;
+            }
+            let _ = Self::ensure_seller(&who, *venus_id)?;
+            if !cirml_artvenuses::Module::<T>::is_holder(*venus_id, &who)? {
+                Err(Error::<T>::NotHolder)?;
+            }
+            Self::ensure_receivers_exist(*venus_id, *part, currency, &who)?;
+            price = price.checked_add(part).ok_or(Error::<T>::Overflow)?;
+        }
+
+        let lot_id = Self::next_lot_id();
+        NextLotId::put(lot_id.checked_add(1).ok_or(Error::<T>::Overflow)?);
+        for (venus_id, _) in venuses.iter() {
+            LotOf::<T>::insert(venus_id, lot_id);
+        }
+        let lot = LotInfo {
+            seller: who.clone(),
+            venuses,
+            price,
+            time: system::Module::<T>::block_number(),
+            currency,
+        };
+        Lots::<T>::insert(lot_id, lot);
+
+        Self::deposit_event(RawEvent::LotOnSell(who, lot_id, price));
+        Ok(())
+    }
+
+    fn deal_lot_impl(
+        buyer: T::AccountId,
+        lot_id: LotId,
+        max_price: T::Balance,
+        time: T::BlockNumber,
+    ) -> DispatchResult {
+        let lot = Self::lots(lot_id).ok_or(Error::<T>::LotNotExist)?;
+        if lot.time != time {
+            Err(Error::<T>::SellChanged)?;
+        }
+        if lot.price > max_price {
+            Err(Error::<T>::PriceTooHigh)?;
+        }
+        if lot.seller == buyer {
+            Err(Error::<T>::DealOwnVenus)?;
+        }
+        // every venus must still be sold by the seller
+        let mut presales: Vec<ArtistId> = vec![];
+        for (venus_id, _) in lot.venuses.iter() {
+            if !Self::is_seller(&lot.seller, *venus_id)?
+                || !cirml_artvenuses::Module::<T>::is_holder(*venus_id, &lot.seller)?
+            {
+                Err(Error::<T>::NotSeller)?;
+            }
+            // the purchases of the lot in the same phase are counted together
+            let artist_id = cirml_artvenuses::Module::<T>::get_artvenus(*venus_id)?.origin;
+            let pending = presales.iter().filter(|id| **id == artist_id).count() as u32;
+            let is_virgin_sell = Self::virgin_sell_out(venus_id).is_none();
+            if let Some(artist_id) =
+                Self::ensure_presale_buyer(&buyer, *venus_id, is_virgin_sell, pending)?
+            {
+                presales.push(artist_id);
+            }
+        }
+
+        Self::settle_all(
+            &buyer,
+            &buyer,
+            &lot.seller,
+            &lot.venuses,
+            lot.currency,
+            false,
+        )?;
+        for artist_id in presales {
+            PresalePurchases::<T>::mutate(artist_id, &buyer, |n| *n = n.saturating_add(1));
+        }
+        Self::remove_lot(lot_id, &lot.venuses);
+
+        Self::deposit_event(RawEvent::LotDeal(buyer, lot_id));
+        Ok(())
+    }
+
+    fn cancel_lot_impl(who: T::AccountId, lot_id: LotId) -> DispatchResult {
+        let lot = Self::lots(lot_id).ok_or(Error::<T>::LotNotExist)?;
+        if lot.seller != who {
+            Err(Error::<T>::NotSeller)?;
+        }
+        Self::remove_lot(lot_id, &lot.venuses);
+
+        Self::deposit_event(RawEvent::LotCancel(who, lot_id));
+        Ok(())
+    }
+
+    fn remove_lot(lot_id: LotId, venuses: &[(ArtvenusId<T>, T::Balance)]) {
+        for (venus_id, _) in venuses.iter() {
+            LotOf::<T>::remove(venus_id);
+        }
+        Lots::<T>::remove(lot_id);
+    }
+}
+
+// english auction
+impl<T: Trait> Module<T> {
+    fn start_auction_impl(
+        who: T::AccountId,
+        venus_id: ArtvenusId<T>,
+        reserve_price: T::Balance,
+        end: T::BlockNumber,
+    ) -> DispatchResult {
+        let _ = Self::ensure_seller(&who, venus_id)?;
+        let now = system::Module::<T>::block_number();
+        if end <= now {
+            Err(Error::<T>::InvalidAuctionEnd)?;
+        }
+        Self::ensure_can_end_at(end)?;
+
+        let sell = OnSellInfo {
+            state: OnSellState::Bidding,
+            price: reserve_price,
+            time: now,
+            buyer: None,
+            expire: None,
+            currency: SellCurrency::Native,
+            lister: who.clone(),
+        };
+        OnSell::<T>::insert(&venus_id, sell);
+        Auctions::<T>::insert(&venus_id, AuctionInfo { end, bid: None });
+        AuctionsEndAt::<T>::insert(end, &venus_id, ());
+
+        Self::deposit_event(RawEvent::AuctionStart(who, venus_id, reserve_price, end));
+        Ok(())
+    }
+
+    /// Ensure that one more auction or raffle could end at `end`.
+    fn ensure_can_end_at(end: T::BlockNumber) -> DispatchResult {
+        let max = T::MaxEndingsPerBlock::get() as usize;
+        let endings = AuctionsEndAt::<T>::iter_prefix(end).take(max).count()
+            + RafflesEndAt::<T>::iter_prefix(end).take(max).count();
+        if endings >= max {
+            Err(Error::<T>::TooManyEndings)?;
+        }
+        Ok(())
+    }
+
+    fn bid_impl(who: T::AccountId, venus_id: ArtvenusId<T>, price: T::Balance) -> DispatchResult {
+        let sell_info = Self::get_on_sell(venus_id)?;
+        if sell_info.state != OnSellState::Bidding {
+            Err(Error::<T>::NotInBidding)?;
+        }
+        let mut auction = Self::auctions(&venus_id).ok_or(Error::<T>::NotInBidding)?;
+        if system::Module::<T>::block_number() > auction.end {
+            Err(Error::<T>::AuctionEnded)?;
+        }
+        if price < sell_info.price {
+            Err(Error::<T>::BidTooLow)?;
+        }
+        if let Some((_, ref current)) = auction.bid {
+            if price <= *current {
+                Err(Error::<T>::BidTooLow)?;
+            }
+        }
+        if Self::is_seller(&who, venus_id)? {
+            Err(Error::<T>::BidOwnAuction)?;
+        }
+
+        <cirml_balances::Module<T> as ReservableCurrency<_>>::reserve(&who, price)
+            .map_err(|_| Error::<T>::CantPay)?;
+        // refund the outbid bidder
+        if let Some((last_bidder, last_price)) = auction.bid.take() {
+            <cirml_balances::Module<T> as ReservableCurrency<_>>::unreserve(
+                &last_bidder,
+                last_price,
+            );
+        }
+        auction.bid = Some((who.clone(), price));
+        Auctions::<T>::insert(&venus_id, auction);
+
+        Self::deposit_event(RawEvent::Bid(who, venus_id, price));
+        Ok(())
+    }
+
+    /// Whether `who` is the seller of `venus_id`, the artist before virgin sell, the holder after.
+    fn is_seller(who: &T::AccountId, venus_id: ArtvenusId<T>) -> Result<bool, DispatchError> {
+        let seller = if Self::virgin_sell_out(&venus_id).is_none() {
+            let artvenus = cirml_artvenuses::Module::<T>::get_artvenus(venus_id)?;
+            cirml_artists::Module::<T>::get_artist_account(artvenus.origin)?
+        } else {
+            cirml_artvenuses::Module::<T>::holder_for(venus_id)?
+        };
+        Ok(seller == *who)
+    }
+
+    fn settle_auction(venus_id: ArtvenusId<T>) {
+        let auction = match Auctions::<T>::take(&venus_id) {
+            Some(auction) => auction,
+            None => return,
+        };
+        if let Some((bidder, price)) = auction.bid {
+            let lister = Self::on_sell(&venus_id).map(|sell_info| sell_info.lister);
+            // the auction is closed without a deal while the market is paused
+            if !Self::paused()
+                && lister.map_or(false, |lister| {
+                    Self::settle(
+                        &bidder,
+                        &lister,
+                        venus_id,
+                        price,
+                        SellCurrency::Native,
+                        true,
+                    )
+                    .is_ok()
+                })
+            {
+                return;
+            }
+            // nothing is changed by a failed settlement, refund the bidder
+            <cirml_balances::Module<T> as ReservableCurrency<_>>::unreserve(&bidder, price);
+        }
+        OnSell::<T>::remove(&venus_id);
+        Self::deposit_event(RawEvent::AuctionFail(venus_id));
+    }
+}
+
+// raffle
+impl<T: Trait> Module<T> {
+    fn start_raffle_impl(
+        who: T::AccountId,
+        venus_id: ArtvenusId<T>,
+        price: T::Balance,
+        end: T::BlockNumber,
+    ) -> DispatchResult {
+        let _ = Self::ensure_seller(&who, venus_id)?;
+        let now = system::Module::<T>::block_number();
+        if end <= now {
+            Err(Error::<T>::InvalidAuctionEnd)?;
+        }
+        Self::ensure_can_end_at(end)?;
+
+        let sell = OnSellInfo {
+            state: OnSellState::Raffle,
+            price,
+            time: now,
+            buyer: None,
+            expire: None,
+            currency: SellCurrency::Native,
+            lister: who.clone(),
+        };
+        OnSell::<T>::insert(&venus_id, sell);
+        Raffles::<T>::insert(
+            &venus_id,
+            RaffleInfo {
+                end,
+                entrants: vec![],
+                price,
+            },
+        );
+        RafflesEndAt::<T>::insert(end, &venus_id, ());
+
+        Self::deposit_event(RawEvent::RaffleStart(who, venus_id, price, end));
+        Ok(())
+    }
+
+    fn enter_raffle_impl(who: T::AccountId, venus_id: ArtvenusId<T>) -> DispatchResult {
+        let _ = Self::get_on_sell(venus_id)?;
+        let mut raffle = Self::raffles(&venus_id).ok_or(Error::<T>::NotInRaffle)?;
+        // the winner could be predicted in the end block
+        if system::Module::<T>::block_number() >= raffle.end {
+            Err(Error::<T>::RaffleEnded)?;
+        }
+        if Self::is_seller(&who, venus_id)? {
+            Err(Error::<T>::EnterOwnRaffle)?;
+        }
+        if raffle.entrants.contains(&who) {
+            Err(Error::<T>::AlreadyEntered)?;
+        }
+        if raffle.entrants.len() >= T::MaxRaffleEntrants::get() as usize {
+            Err(Error::<T>::RaffleFull)?;
+        }
+
+        <cirml_balances::Module<T> as ReservableCurrency<_>>::reserve(&who, raffle.price)
+            .map_err(|_| Error::<T>::CantPay)?;
+        raffle.entrants.push(who.clone());
+        Raffles::<T>::insert(&venus_id, raffle);
+
+        Self::deposit_event(RawEvent::RaffleEnter(who, venus_id));
+        Ok(())
+    }
+
+    /// Draw the winner of the raffle of `venus_id` and refund the others.
+    fn draw_raffle(venus_id: ArtvenusId<T>) {
+        let raffle = match Raffles::<T>::take(&venus_id) {
+            Some(raffle) => raffle,
+            None => return,
+        };
+        let price = raffle.price;
+        let lister = Self::on_sell(&venus_id).map(|sell_info| sell_info.lister);
+        let mut entrants = raffle.entrants;
+        let mut winner = None;
+        // the raffle is closed without a deal while the market is paused, and the entrants are
+        // refunded even if the sell order is gone
+        if let Some(lister) = lister.filter(|_| !entrants.is_empty() && !Self::paused()) {
+            let random = T::Randomness::random(&(b"market/raffle", venus_id).encode());
+            let index =
+                u64::decode(&mut random.as_ref()).unwrap_or_default() % entrants.len() as u64;
+            let candidate = entrants.swap_remove(index as usize);
+            if Self::settle(
+                &candidate,
+                &lister,
+                venus_id,
+                price,
+                SellCurrency::Native,
+                true,
+            )
+            .is_ok()
+            {
+                winner = Some(candidate);
+            } else {
+                // nothing is changed by a failed settlement
+                entrants.push(candidate);
+            }
+        }
+        for entrant in entrants.iter() {
+            <cirml_balances::Module<T> as ReservableCurrency<_>>::unreserve(entrant, price);
+        }
+        match winner {
+            Some(winner) => Self::deposit_event(RawEvent::RaffleWin(winner, venus_id)),
+            None => {
+                Self::remove_on_sell(venus_id);
+                Self::deposit_event(RawEvent::RaffleFail(venus_id));
+            }
+        }
+    }
+}
+
+// offer
+impl<T: Trait> Module<T> {
+    fn make_offer_impl(
+        who: T::AccountId,
+        venus_id: ArtvenusId<T>,
+        price: T::Balance,
+        expire: T::BlockNumber,
+    ) -> DispatchResult {
+        let _ = cirml_artvenuses::Module::<T>::get_artvenus(venus_id)?;
+        if cirml_artvenuses::Module::<T>::is_holder(venus_id, &who)? {
+            Err(Error::<T>::OfferOwnVenus)?;
+        }
+        if expire <= system::Module::<T>::block_number() {
+            Err(Error::<T>::InvalidExpire)?;
+        }
+        if price < T::MinOfferPrice::get() {
+            Err(Error::<T>::OfferTooLow)?;
+        }
+        let last = Self::offers(&venus_id, &who);
+        let is_new = last.is_none();
+        if is_new && Self::offer_counts(&venus_id) >= T::MaxOffersPerVenus::get() {
+            Err(Error::<T>::TooManyOffers)?;
+        }
+
+        let last_price = last.map(|offer| offer.price).unwrap_or_else(Zero::zero);
+        <cirml_balances::Module<T> as ReservableCurrency<_>>::reserve(&who, price)
+            .map_err(|_| Error::<T>::CantPay)?;
+        // the new offer replaces the last one
+        <cirml_balances::Module<T> as ReservableCurrency<_>>::unreserve(&who, last_price);
+        if is_new {
+            OfferCounts::<T>::mutate(&venus_id, |count| *count += 1);
+        }
+        Offers::<T>::insert(&venus_id, &who, OfferInfo { price, expire });
+
+        Self::deposit_event(RawEvent::Offer(who, venus_id, price, expire));
+        Ok(())
+    }
+
+    fn accept_offer_impl(
+        who: T::AccountId,
+        venus_id: ArtvenusId<T>,
+        buyer: T::AccountId,
+    ) -> DispatchResult {
+        if !cirml_artvenuses::Module::<T>::is_holder(venus_id, &who)? {
+            Err(Error::<T>::NotHolder)?;
+        }
+        if let Some(sell_info) = Self::on_sell(&venus_id) {
+            if sell_info.state.is_reserving() {
+                Err(Error::<T>::InBidding)?;
+            }
+        }
+        if LotOf::<T>::contains_key(&venus_id) {
+            Err(Error::<T>::InLot)?;
+        }
+        Self::ensure_not_in_cooldown(venus_id)?;
+        let offer = Self::offers(&venus_id, &buyer).ok_or(Error::<T>::OfferNotExist)?;
+        if offer.expire <= system::Module::<T>::block_number() {
+            Err(Error::<T>::OfferExpired)?;
+        }
+
+        Self::settle(
+            &buyer,
+            &who,
+            venus_id,
+            offer.price,
+            SellCurrency::Native,
+            true,
+        )?;
+        Offers::<T>::remove(&venus_id, &buyer);
+        // refund all competing offers, bounded by `MaxOffersPerVenus`
+        let others: Vec<_> = Offers::<T>::iter_prefix(&venus_id).collect();
+        for (other, offer) in others {
+            Self::cancel_offer(&other, venus_id, offer);
+        }
+        OfferCounts::<T>::remove(&venus_id);
+        Ok(())
+    }
+
+    fn cancel_offer(
+        who: &T::AccountId,
+        venus_id: ArtvenusId<T>,
+        offer: OfferInfo<T::Balance, T::BlockNumber>,
+    ) {
+        <cirml_balances::Module<T> as ReservableCurrency<_>>::unreserve(who, offer.price);
+        Offers::<T>::remove(&venus_id, who);
+        OfferCounts::<T>::mutate(&venus_id, |count| *count = count.saturating_sub(1));
+        Self::deposit_event(RawEvent::OfferCancel(who.clone(), venus_id));
+    }
+}
+
+// swap
+impl<T: Trait> Module<T> {
+    fn propose_swap_impl(
+        who: T::AccountId,
+        give: ArtvenusId<T>,
+        take: ArtvenusId<T>,
+        top_up: T::Balance,
+        valuation: T::Balance,
+    ) -> DispatchResult {
+        if give == take
+            || !cirml_artvenuses::Module::<T>::is_holder(give, &who)?
+            || cirml_artvenuses::Module::<T>::is_holder(take, &who)?
+        {
+            Err(Error::<T>::InvalidSwap)?;
+        }
+        Self::ensure_swappable(give)?;
+        Self::ensure_swappable(take)?;
+        Self::ensure_not_in_cooldown(give)?;
+        let take_valuation = valuation.checked_add(&top_up).ok_or(Error::<T>::Overflow)?;
+        // a venus could not be swapped under its value to avoid the royalty
+        if valuation < Self::swap_floor(give) || take_valuation < Self::swap_floor(take) {
+            Err(Error::<T>::ValuationTooLow)?;
+        }
+        let (_, royalty) = Self::swap_royalty(give, valuation)?;
+        let reserved = top_up.checked_add(&royalty).ok_or(Error::<T>::Overflow)?;
+
+        let swap_id = Self::next_swap_id();
+        let next_id = swap_id.checked_add(1).ok_or(Error::<T>::Overflow)?;
+        <cirml_balances::Module<T> as ReservableCurrency<_>>::reserve(&who, reserved)
+            .map_err(|_| Error::<T>::CantPay)?;
+        NextSwapId::put(next_id);
+        let swap = SwapInfo {
+            proposer: who.clone(),
+            give,
+            take,
+            top_up,
+            valuation,
+            royalty,
+        };
+        Swaps::<T>::insert(swap_id, swap);
+        SwapsOf::<T>::insert(&give, swap_id, ());
+        SwapsOf::<T>::insert(&take, swap_id, ());
+
+        Self::deposit_event(RawEvent::SwapPropose(who, swap_id, give, take, top_up));
+        Ok(())
+    }
+
+    fn accept_swap_impl(who: T::AccountId, swap_id: SwapId) -> DispatchResult {
+        let swap = Self::swaps(swap_id).ok_or(Error::<T>::SwapNotExist)?;
+        if !cirml_artvenuses::Module::<T>::is_holder(swap.take, &who)? {
+            Err(Error::<T>::NotHolder)?;
+        }
+        if !cirml_artvenuses::Module::<T>::is_holder(swap.give, &swap.proposer)? {
+            Err(Error::<T>::InvalidSwap)?;
+        }
+        Self::ensure_swappable(swap.give)?;
+        Self::ensure_swappable(swap.take)?;
+        Self::ensure_not_in_cooldown(swap.give)?;
+        Self::ensure_not_in_cooldown(swap.take)?;
+
+        // the proposer pays the top-up to the accepter and the royalty of `give`
+        let (give_artist, _) = Self::swap_royalty(swap.give, swap.valuation)?;
+        let proposer_receivers = vec![(who.clone(), swap.top_up), (give_artist, swap.royalty)];
+        // the accepter pays the royalty of `take`
+        let take_valuation = swap
+            .valuation
+            .checked_add(&swap.top_up)
+            .ok_or(Error::<T>::Overflow)?;
+        let (take_artist, take_royalty) = Self::swap_royalty(swap.take, take_valuation)?;
+        let accepter_receivers = vec![(take_artist, take_royalty)];
+        Self::ensure_can_settle(
+            &swap.proposer,
+            &[(swap.give, swap.valuation)],
+            swap.top_up + swap.royalty,
+            SellCurrency::Native,
+            true,
+            &proposer_receivers,
+        )?;
+        Self::ensure_can_settle(
+            &who,
+            &[(swap.take, take_valuation)],
+            take_royalty,
+            SellCurrency::Native,
+            false,
+            &accepter_receivers,
+        )?;
+
+        <cirml_balances::Module<T> as ReservableCurrency<_>>::reserve(&who, take_royalty)
+            .map_err(|_| Error::<T>::CantPay)?;
+        Self::repatriate_all(SellCurrency::Native, &swap.proposer, &proposer_receivers)?;
+        Self::repatriate_all(SellCurrency::Native, &who, &accepter_receivers)?;
+        cirml_artvenuses::Module::<T>::move_artvenus(
+            swap.give,
+            &who,
+            AcquisitionType::Sold,
+            Some((swap.valuation, SellCurrency::Native)),
+        )?;
+        cirml_artvenuses::Module::<T>::move_artvenus(
+            swap.take,
+            &swap.proposer,
+            AcquisitionType::Sold,
+            Some((take_valuation, SellCurrency::Native)),
+        )?;
+        Consignments::<T>::remove(&swap.give);
+        Consignments::<T>::remove(&swap.take);
+        Self::remove_swap(swap_id, &swap);
+
+        Self::deposit_event(RawEvent::SwapDone(swap.proposer, who, swap_id));
+        Ok(())
+    }
+
+    fn cancel_swap_impl(swap_id: SwapId, swap: SwapInfo<T::AccountId, ArtvenusId<T>, T::Balance>) {
+        <cirml_balances::Module<T> as ReservableCurrency<_>>::unreserve(
+            &swap.proposer,
+            swap.top_up + swap.royalty,
+        );
+        Self::remove_swap(swap_id, &swap);
+        Self::deposit_event(RawEvent::SwapCancel(swap.proposer, swap_id));
+    }
+
+    fn remove_swap(swap_id: SwapId, swap: &SwapInfo<T::AccountId, ArtvenusId<T>, T::Balance>) {
+        Swaps::<T>::remove(swap_id);
+        SwapsOf::<T>::remove(&swap.give, swap_id);
+        SwapsOf::<T>::remove(&swap.take, swap_id);
+    }
+
+    /// A venus in swap must have been sold in virgin sell and not be on sell. Its last sale must
+    /// be in `SellCurrency::Native`, which the valuation is floored by.
+    fn ensure_swappable(venus_id: ArtvenusId<T>) -> DispatchResult {
+        if Self::virgin_sell_out(&venus_id).is_none() {
+            Err(Error::<T>::VirginInSwap)?;
+        }
+        if Self::last_sale(&venus_id)
+            .map_or(false, |record| record.currency != SellCurrency::Native)
+        {
+            Err(Error::<T>::StableInSwap)?;
+        }
+        if OnSell::<T>::contains_key(&venus_id) {
+            Err(Error::<T>::AlreadyOnSell)?;
+        }
+        if LotOf::<T>::contains_key(&venus_id) {
+            Err(Error::<T>::InLot)?;
+        }
+        Ok(())
+    }
+
+    /// The least valuation of `venus_id` in a swap, that's the last sale price if it's over
+    /// `MinSwapValuation`.
+    fn swap_floor(venus_id: ArtvenusId<T>) -> T::Balance {
+        Self::last_sale(&venus_id)
+            .map_or_else(Zero::zero, |record| record.price)
+            .max(T::MinSwapValuation::get())
+    }
+
+    /// The artist account and the royalty of `venus_id` valued at `valuation`.
+    fn swap_royalty(
+        venus_id: ArtvenusId<T>,
+        valuation: T::Balance,
+    ) -> Result<(T::AccountId, T::Balance), DispatchError> {
+        let artvenus = cirml_artvenuses::Module::<T>::get_artvenus(venus_id)?;
+        let artist = cirml_artists::Module::<T>::get_artist_account(artvenus.origin)?;
+        let royalty = Self::normal_fee_schedule().royalty.mul_floor(valuation);
+        Ok((artist, royalty))
+    }
+}
+
+// consignment
+impl<T: Trait> Module<T> {
+    fn consign_impl(
+        who: T::AccountId,
+        venus_id: ArtvenusId<T>,
+        consignee: T::AccountId,
+        commission: Percent,
+    ) -> DispatchResult {
+        if !cirml_artvenuses::Module::<T>::is_holder(venus_id, &who)? {
+            Err(Error::<T>::NotHolder)?;
+        }
+        if Self::galleries(&consignee).is_none() {
+            Err(Error::<T>::NotGallery)?;
+        }
+        // the terms could not be changed under a sell order
+        if OnSell::<T>::contains_key(&venus_id) {
+            Err(Error::<T>::AlreadyOnSell)?;
+        }
+        let schedule = if Self::virgin_sell_out(&venus_id).is_none() {
+            Self::virgin_fee_schedule()
+        } else {
+            Self::normal_fee_schedule()
+        };
+        if !schedule.can_add(commission) {
+            Err(Error::<T>::InvalidCommission)?;
+        }
+
+        Consignments::<T>::insert(
+            &venus_id,
+            ConsignInfo {
+                consignee: consignee.clone(),
+                commission,
+            },
+        );
+        Self::deposit_event(RawEvent::Consign(who, venus_id, consignee, commission));
+        Ok(())
+    }
+
+    fn revoke_consign_impl(who: T::AccountId, venus_id: ArtvenusId<T>) -> DispatchResult {
+        if !cirml_artvenuses::Module::<T>::is_holder(venus_id, &who)? {
+            Err(Error::<T>::NotHolder)?;
+        }
+        if !Consignments::<T>::contains_key(&venus_id) {
+            Err(Error::<T>::NotConsigned)?;
+        }
+        if let Some(sell_info) = Self::on_sell(&venus_id) {
+            if sell_info.state.is_reserving() {
+                Err(Error::<T>::InBidding)?;
+            }
+            Self::remove_on_sell(venus_id);
+            Self::deposit_event(RawEvent::CancelSell(who.clone(), venus_id));
+        }
+
+        Consignments::<T>::remove(&venus_id);
+        Self::deposit_event(RawEvent::ConsignRevoke(who, venus_id));
+        Ok(())
+    }
+
+    /// The account `who` acts for on `venus_id`, it's the holder if `who` is the consignee.
+    fn acting_for(who: &T::AccountId, venus_id: ArtvenusId<T>) -> T::AccountId {
+        match Self::consignments(&venus_id) {
+            Some(consign) if consign.consignee == *who && Self::galleries(who).is_some() => {
+                cirml_artvenuses::Module::<T>::holder_for(venus_id).unwrap_or_else(|_| who.clone())
+            }
+            _ => who.clone(),
+        }
+    }
+
+    /// The consignee and the commission in a deal of `venus_id`, only if the consignee is the
+    /// `lister` of the deal.
+    fn commission_of(
+        venus_id: ArtvenusId<T>,
+        lister: &T::AccountId,
+    ) -> Option<(T::AccountId, Percent)> {
+        Self::consignments(&venus_id)
+            .filter(|consign| {
+                consign.consignee == *lister && Self::galleries(&consign.consignee).is_some()
+            })
+            .map(|consign| (consign.consignee, consign.commission))
+    }
+}
+
+impl<T: Trait> cirml_artvenuses::ArtvenusMarket<ArtvenusId<T>> for Module<T> {
+    /// The venus is in a sell order or a lot.
+    fn is_listed(id: ArtvenusId<T>) -> bool {
+        OnSell::<T>::contains_key(&id) || LotOf::<T>::contains_key(&id)
+    }
+
+    fn is_sold(id: ArtvenusId<T>) -> bool {
+        VirginSellOut::<T>::contains_key(&id)
+    }
+
+    /// The consignment is made by the former holder.
+    fn on_transfer(id: ArtvenusId<T>) {
+        Consignments::<T>::remove(&id);
+    }
+
+    /// The sell order, the lot, the offers, the swaps and the consignment of the venus are
+    /// dropped, the balances reserved for them are refunded. Its sales are dropped too, as the id
+    /// of a squatting venus is created again.
+    fn on_burn(id: ArtvenusId<T>) {
+        if let Some(auction) = Auctions::<T>::take(&id) {
+            if let Some((bidder, price)) = auction.bid {
+                <cirml_balances::Module<T> as ReservableCurrency<_>>::unreserve(&bidder, price);
+            }
+            AuctionsEndAt::<T>::remove(auction.end, &id);
+        }
+        if let Some(raffle) = Raffles::<T>::take(&id) {
+            for entrant in raffle.entrants.iter() {
+                <cirml_balances::Module<T> as ReservableCurrency<_>>::unreserve(
+                    entrant,
+                    raffle.price,
+                );
+            }
+            RafflesEndAt::<T>::remove(raffle.end, &id);
+        }
+        Self::remove_on_sell(id);
+        if let Some(lot_id) = Self::lot_of(&id) {
+            if let Some(lot) = Self::lots(lot_id) {
+                Self::remove_lot(lot_id, &lot.venuses);
+            }
+        }
+        for (buyer, offer) in Offers::<T>::iter_prefix(&id) {
+            <cirml_balances::Module<T> as ReservableCurrency<_>>::unreserve(&buyer, offer.price);
+        }
+        Offers::<T>::remove_prefix(&id);
+        OfferCounts::<T>::remove(&id);
+        let swaps: Vec<_> = SwapsOf::<T>::iter_prefix(&id)
+            .filter_map(|(swap_id, _)| Self::swaps(swap_id).map(|swap| (swap_id, swap)))
+            .collect();
+        for (swap_id, swap) in swaps {
+            Self::cancel_swap_impl(swap_id, swap);
+        }
+        Consignments::<T>::remove(&id);
+        VirginSellOut::<T>::remove(&id);
+        LastSale::<T>::remove(&id);
+    }
+}
+
+// for runtime-api
+impl<T: Trait> Module<T> {
+    pub fn offer_list(
+        venus_id: ArtvenusId<T>,
+    ) -> Vec<(T::AccountId, OfferInfo<T::Balance, T::BlockNumber>)> {
+        Offers::<T>::iter_prefix(&venus_id).collect()
+    }
+
+    /// The price of a dutch auction is reported as the current price, the expired sell orders
+    /// are excluded.
+    pub fn on_sell_list() -> Vec<(
+        ArtvenusId<T>,
+        OnSellInfo<T::AccountId, T::Balance, T::BlockNumber>,
+    )> {
+        OnSell::<T>::iter()
+            .filter(|(_, sell_info)| !Self::is_expired(sell_info))
+            .map(|(venus_id, mut sell_info)| {
+                sell_info.price = Self::current_price(venus_id, &sell_info);
+                (venus_id, sell_info)
+            })
+            .collect()
+    }
+
+    pub fn quote_deal(venus_id: ArtvenusId<T>) -> Option<DealQuote<T::AccountId, T::Balance>> {
+        let sell_info = Self::on_sell(venus_id)?;
+        if Self::is_expired(&sell_info) {
+            return None;
+        }
+        Self::quote(
+            venus_id,
+            Self::current_price(venus_id, &sell_info),
+            &sell_info.lister,
+        )
+        .ok()
     }
 }
@@ -0,0 +1,279 @@
+//! Storage migrations of the market.
+
+use codec::{Decode, Encode};
+use frame_support::{
+    storage::migration::{put_storage_value, take_storage_value, StorageIterator},
+    traits::Get,
+    weights::Weight,
+    IterableStorageDoubleMap, IterableStorageMap, StorageDoubleMap, StorageMap, StorageValue,
+};
+use sp_runtime::{PerThing, Percent};
+use sp_std::prelude::*;
+
+use crate::{
+    Consignments, ExpireCursor, FeeSchedule, LotInfo, NormalFeeSchedule, OfferCounts, Offers,
+    OnSell, OnSellInfo, OnSellState, RaffleInfo, Releases, SellCurrency, StorageVersion, Swaps,
+    SwapsOf, Trait, VirginFeeSchedule,
+};
+
+pub fn on_runtime_upgrade<T: Trait>() -> Weight {
+    let version = StorageVersion::get();
+    if version == Releases::V10_0_0 {
+        return 0;
+    }
+    if version < Releases::V2_0_0 {
+        migrate_to_v2::<T>();
+    }
+    if version < Releases::V3_0_0 {
+        migrate_to_v3::<T>();
+    }
+    if version < Releases::V4_0_0 {
+        migrate_to_v4::<T>();
+    }
+    if version < Releases::V5_0_0 {
+        migrate_to_v5::<T>();
+    }
+    if version < Releases::V6_0_0 {
+        migrate_to_v6::<T>();
+    }
+    if version < Releases::V7_0_0 {
+        migrate_to_v7::<T>();
+    }
+    if version < Releases::V8_0_0 {
+        migrate_to_v8::<T>();
+    }
+    if version < Releases::V9_0_0 {
+        migrate_to_v9::<T>();
+    }
+    if version < Releases::V10_0_0 {
+        migrate_to_v10::<T>();
+    }
+    StorageVersion::put(Releases::V10_0_0);
+    T::MaximumBlockWeight::get()
+}
+
+/// `VirginSellPercent` and `NormalSellPercent` (the part for the artist) are replaced by the fee
+/// schedules.
+fn migrate_to_v2<T: Trait>() {
+    if let Some(percent) = take_storage_value::<Percent>(b"Market", b"VirginSellPercent", &[]) {
+        // the artist is the seller in a virgin sell and receives the rest
+        VirginFeeSchedule::<T>::put(FeeSchedule {
+            platform: Percent::from_parts(100 - percent.deconstruct()),
+            royalty: Percent::from_percent(0),
+            beneficiaries: vec![],
+        });
+    }
+    if let Some(percent) = take_storage_value::<Percent>(b"Market", b"NormalSellPercent", &[]) {
+        NormalFeeSchedule::<T>::put(FeeSchedule {
+            platform: Percent::from_percent(0),
+            royalty: percent,
+            beneficiaries: vec![],
+        });
+    }
+}
+
+/// `OnSellInfo` before the designated buyer is added.
+#[derive(Encode, Decode)]
+struct OnSellInfoV2<Balance, BlockNumber> {
+    state: OnSellState,
+    price: Balance,
+    time: BlockNumber,
+}
+
+/// All existing sell orders are public.
+fn migrate_to_v3<T: Trait>() {
+    let old: Vec<_> =
+        StorageIterator::<OnSellInfoV2<T::Balance, T::BlockNumber>>::new(b"Market", b"OnSell")
+            .drain()
+            .collect();
+    for (key, info) in old {
+        let info: OnSellInfoV3<T::AccountId, _, _> = OnSellInfoV3 {
+            state: info.state,
+            price: info.price,
+            time: info.time,
+            buyer: None,
+        };
+        put_storage_value(b"Market", b"OnSell", &key, info);
+    }
+}
+
+/// `OnSellInfo` before the expiry is added.
+#[derive(Encode, Decode)]
+struct OnSellInfoV3<AccountId, Balance, BlockNumber> {
+    state: OnSellState,
+    price: Balance,
+    time: BlockNumber,
+    buyer: Option<AccountId>,
+}
+
+/// All existing sell orders never expire.
+fn migrate_to_v4<T: Trait>() {
+    let old: Vec<_> =
+        StorageIterator::<OnSellInfoV3<T::AccountId, T::Balance, T::BlockNumber>>::new(
+            b"Market", b"OnSell",
+        )
+        .drain()
+        .collect();
+    for (key, info) in old {
+        let info = OnSellInfoV4::<T::AccountId, _, _> {
+            state: info.state,
+            price: info.price,
+            time: info.time,
+            buyer: info.buyer,
+            expire: None,
+        };
+        put_storage_value(b"Market", b"OnSell", &key, info);
+    }
+    ExpireCursor::<T>::put(frame_system::Module::<T>::block_number());
+}
+
+/// `OnSellInfo` before the currency is added.
+#[derive(Encode, Decode)]
+struct OnSellInfoV4<AccountId, Balance, BlockNumber> {
+    state: OnSellState,
+    price: Balance,
+    time: BlockNumber,
+    buyer: Option<AccountId>,
+    expire: Option<BlockNumber>,
+}
+
+/// All existing sell orders are in the native currency.
+fn migrate_to_v5<T: Trait>() {
+    let old: Vec<_> =
+        StorageIterator::<OnSellInfoV4<T::AccountId, T::Balance, T::BlockNumber>>::new(
+            b"Market", b"OnSell",
+        )
+        .drain()
+        .collect();
+    for (key, info) in old {
+        let info = OnSellInfoV7::<T::AccountId, _, _> {
+            state: info.state,
+            price: info.price,
+            time: info.time,
+            buyer: info.buyer,
+            expire: info.expire,
+            currency: SellCurrency::Native,
+        };
+        put_storage_value(b"Market", b"OnSell", &key, info);
+    }
+}
+
+/// The existing offers are counted for `MaxOffersPerVenus`.
+fn migrate_to_v6<T: Trait>() {
+    for (venus_id, _) in cirml_artvenuses::ArtvenusInfos::<T>::iter() {
+        let count = Offers::<T>::iter_prefix(&venus_id).count() as u32;
+        if count > 0 {
+            OfferCounts::<T>::insert(&venus_id, count);
+        }
+    }
+}
+
+/// `LotInfo` before the currency is added.
+#[derive(Encode, Decode)]
+struct LotInfoV6<AccountId, ArtvenusId, Balance, BlockNumber> {
+    seller: AccountId,
+    venuses: Vec<(ArtvenusId, Balance)>,
+    price: Balance,
+    time: BlockNumber,
+}
+
+/// All existing lots are in the native currency.
+fn migrate_to_v7<T: Trait>() {
+    let old: Vec<_> = StorageIterator::<
+        LotInfoV6<T::AccountId, cirml_artvenuses::ArtvenusId<T>, T::Balance, T::BlockNumber>,
+    >::new(b"Market", b"Lots")
+    .drain()
+    .collect();
+    for (key, lot) in old {
+        let lot = LotInfo {
+            seller: lot.seller,
+            venuses: lot.venuses,
+            price: lot.price,
+            time: lot.time,
+            currency: SellCurrency::Native,
+        };
+        put_storage_value(b"Market", b"Lots", &key, lot);
+    }
+}
+
+/// `OnSellInfo` before the lister is added.
+#[derive(Encode, Decode)]
+struct OnSellInfoV7<AccountId, Balance, BlockNumber> {
+    state: OnSellState,
+    price: Balance,
+    time: BlockNumber,
+    buyer: Option<AccountId>,
+    expire: Option<BlockNumber>,
+    currency: SellCurrency,
+}
+
+/// The existing sell orders of consigned venuses are listed by the consignee, as the commission
+/// was charged on them, others by the holder.
+fn migrate_to_v8<T: Trait>() {
+    let old: Vec<_> =
+        StorageIterator::<OnSellInfoV7<T::AccountId, T::Balance, T::BlockNumber>>::new(
+            b"Market", b"OnSell",
+        )
+        .drain()
+        .collect();
+    for (key, info) in old {
+        // the key is not hashed
+        let venus_id = match cirml_artvenuses::ArtvenusId::<T>::decode(&mut &key[..]) {
+            Ok(venus_id) => venus_id,
+            Err(_) => continue,
+        };
+        let lister = match Consignments::<T>::get(&venus_id) {
+            Some(consign) => consign.consignee,
+            None => cirml_artvenuses::Module::<T>::holder_for(venus_id).unwrap_or_default(),
+        };
+        let info = OnSellInfo {
+            state: info.state,
+            price: info.price,
+            time: info.time,
+            buyer: info.buyer,
+            expire: info.expire,
+            currency: info.currency,
+            lister,
+        };
+        put_storage_value(b"Market", b"OnSell", &key, info);
+    }
+}
+
+/// `RaffleInfo` before the price is added.
+#[derive(Encode, Decode)]
+struct RaffleInfoV8<AccountId, BlockNumber> {
+    end: BlockNumber,
+    entrants: Vec<AccountId>,
+}
+
+/// The price of every existing raffle is the price of its sell order.
+fn migrate_to_v9<T: Trait>() {
+    let old: Vec<_> =
+        StorageIterator::<RaffleInfoV8<T::AccountId, T::BlockNumber>>::new(b"Market", b"Raffles")
+            .drain()
+            .collect();
+    for (key, raffle) in old {
+        // the key is not hashed
+        let venus_id = match cirml_artvenuses::ArtvenusId::<T>::decode(&mut &key[..]) {
+            Ok(venus_id) => venus_id,
+            Err(_) => continue,
+        };
+        let price = OnSell::<T>::get(&venus_id)
+            .map(|sell_info| sell_info.price)
+            .unwrap_or_default();
+        let raffle = RaffleInfo {
+            end: raffle.end,
+            entrants: raffle.entrants,
+            price,
+        };
+        put_storage_value(b"Market", b"Raffles", &key, raffle);
+    }
+}
+
+/// The existing swaps are indexed by their venuses.
+fn migrate_to_v10<T: Trait>() {
+    for (swap_id, swap) in Swaps::<T>::iter() {
+        SwapsOf::<T>::insert(&swap.give, swap_id, ());
+        SwapsOf::<T>::insert(&swap.take, swap_id, ());
+    }
+}
@@ -0,0 +1,228 @@
+//! Test utilities
+
+#![cfg(test)]
+
+use frame_support::{
+    impl_outer_dispatch, impl_outer_event, impl_outer_origin, parameter_types,
+    traits::{Randomness, StorageMapShim},
+    weights::Weight,
+};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, Hash, IdentityLookup},
+    Perbill, Percent,
+};
+
+use ci_primitives::Text;
+use cirml_artists::{ArtistInfo, Gender};
+
+use crate::{FeeSchedule, GenesisConfig, Module, Trait};
+
+use frame_system as system;
+impl_outer_origin! {
+    pub enum Origin for Test {}
+}
+
+mod market {
+    pub use crate::{Call, Event};
+}
+
+impl_outer_event! {
+    pub enum TestEvent for Test {
+        system<T>,
+        market<T>,
+    }
+}
+
+impl_outer_dispatch! {
+    pub enum Call for Test where origin: Origin {
+        market::Market,
+    }
+}
+
+// Workaround for https://github.com/rust-lang/rust/issues/26925 . Remove when sorted.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Test;
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::one();
+    pub const ExistentialDeposit: u64 = 1;
+    pub const MinVirginSellPercent: Percent = Percent::from_percent(50);
+    pub const MaxNormalSellPercent: Percent = Percent::from_percent(20);
+    pub const MaxExpiredSellsPerBlock: u32 = 2;
+    pub const MaxRaffleEntrants: u32 = 2;
+    pub const MinSwapValuation: u64 = 10;
+    pub const MaxEndingsPerBlock: u32 = 2;
+    pub const MinOfferPrice: u64 = 10;
+    pub const MaxOffersPerVenus: u32 = 2;
+}
+impl frame_system::Trait for Test {
+    type Origin = Origin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Call = Call;
+    type Hash = H256;
+    type Hashing = ::sp_runtime::traits::BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = TestEvent;
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type ModuleToIndex = ();
+    type AccountData = cirml_balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+}
+impl cirml_balances::Trait for Test {
+    type Balance = u64;
+    type DustRemoval = ();
+    type Event = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+}
+impl cirml_balances::Trait<cirml_balances::Instance1> for Test {
+    type Balance = u64;
+    type DustRemoval = ();
+    type Event = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = StorageMapShim<
+        cirml_balances::Account<Test, cirml_balances::Instance1>,
+        system::CallOnCreatedAccount<Test>,
+        system::CallKillAccount<Test>,
+        u64,
+        cirml_balances::AccountData<u64>,
+    >;
+}
+impl cirml_artists::Trait for Test {
+    type Event = ();
+}
+impl cirml_artvenuses::Trait for Test {
+    type Hash = H256;
+    type Event = ();
+    type Market = Market;
+}
+impl Trait for Test {
+    type Event = TestEvent;
+    type StableCurrency = StableBalances;
+    type MinVirginSellPercent = MinVirginSellPercent;
+    type MaxNormalSellPercent = MaxNormalSellPercent;
+    type MaxExpiredSellsPerBlock = MaxExpiredSellsPerBlock;
+    type Randomness = TestRandomness;
+    type MaxRaffleEntrants = MaxRaffleEntrants;
+    type MinSwapValuation = MinSwapValuation;
+    type MaxEndingsPerBlock = MaxEndingsPerBlock;
+    type MinOfferPrice = MinOfferPrice;
+    type MaxOffersPerVenus = MaxOffersPerVenus;
+}
+
+/// A deterministic randomness, the same subject is always drawn to the same output.
+pub struct TestRandomness;
+impl Randomness<H256> for TestRandomness {
+    fn random(subject: &[u8]) -> H256 {
+        BlakeTwo256::hash(subject)
+    }
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = cirml_balances::Module<Test>;
+pub type StableBalances = cirml_balances::Module<Test, cirml_balances::Instance1>;
+pub type Artists = cirml_artists::Module<Test>;
+pub type Artvenuses = cirml_artvenuses::Module<Test>;
+pub type Market = Module<Test>;
+
+pub fn last_event() -> TestEvent {
+    System::events().pop().expect("Event expected").event
+}
+
+pub const MANAGER: u64 = 100;
+pub const ARTIST: u64 = 1;
+pub const ALICE: u64 = 2;
+pub const BOB: u64 = 3;
+/// An account without any balance.
+pub const NOBODY: u64 = 99;
+
+/// The venus created by `ARTIST` in every test.
+pub fn venus() -> H256 {
+    H256::repeat_byte(1)
+}
+
+pub fn text(s: &str) -> Text {
+    s.as_bytes().to_vec()
+}
+
+pub fn fee_schedule(platform: u8, royalty: u8) -> FeeSchedule<u64> {
+    FeeSchedule {
+        platform: Percent::from_percent(platform),
+        royalty: Percent::from_percent(royalty),
+        beneficiaries: vec![],
+    }
+}
+
+pub struct ExtBuilder {
+    virgin_fee_schedule: FeeSchedule<u64>,
+    normal_fee_schedule: FeeSchedule<u64>,
+}
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self {
+            virgin_fee_schedule: fee_schedule(20, 0),
+            normal_fee_schedule: fee_schedule(0, 2),
+        }
+    }
+}
+impl ExtBuilder {
+    pub fn virgin_fee_schedule(mut self, schedule: FeeSchedule<u64>) -> Self {
+        self.virgin_fee_schedule = schedule;
+        self
+    }
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut t = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+        cirml_balances::GenesisConfig::<Test> {
+            balances: vec![(MANAGER, 1000), (ARTIST, 1000), (ALICE, 1000), (BOB, 1000)],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+        cirml_balances::GenesisConfig::<Test, cirml_balances::Instance1> {
+            balances: vec![(MANAGER, 1000), (ARTIST, 1000), (ALICE, 1000)],
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+        GenesisConfig::<Test> {
+            manager: MANAGER,
+            virgin_fee_schedule: self.virgin_fee_schedule,
+            normal_fee_schedule: self.normal_fee_schedule,
+        }
+        .assimilate_storage(&mut t)
+        .unwrap();
+
+        let mut ext: sp_io::TestExternalities = t.into();
+        ext.execute_with(|| {
+            System::set_block_number(1);
+            Artists::regist_artist(
+                system::RawOrigin::Root.into(),
+                ARTIST,
+                ArtistInfo {
+                    name: text("artist"),
+                    gender: Gender::Male,
+                },
+            )
+            .unwrap();
+            Artvenuses::create_artvenus(Origin::signed(ARTIST), venus(), text("venus"), text(""))
+                .unwrap();
+        });
+        ext
+    }
+}
@@ -0,0 +1,1570 @@
+//! Tests for the module.
+
+#![cfg(test)]
+
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{LockableCurrency, OnFinalize, OnInitialize, WithdrawReasons},
+    weights::DispatchInfo,
+    StorageMap,
+};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+    traits::{BadOrigin, BlakeTwo256, Hash, SignedExtension},
+    transaction_validity::InvalidTransaction,
+    DispatchResult, Percent,
+};
+
+use cirml_artvenuses::AcquisitionType;
+
+use crate::extension::MARKET_PAUSED;
+use crate::mock::*;
+use crate::{
+    Allowlist, CheckMarketPaused, Error, OnSell, PriceDecay, RawEvent, SaleRecord, SalesStats,
+    SellCurrency,
+};
+
+fn holder() -> u64 {
+    Artvenuses::holder_for(venus()).unwrap()
+}
+
+fn create_venus(id: H256) {
+    assert_ok!(Artvenuses::create_artvenus(
+        Origin::signed(ARTIST),
+        id,
+        text("venus"),
+        text("")
+    ));
+}
+
+fn deal(buyer: u64) -> DispatchResult {
+    let time = Market::on_sell(venus()).map(|s| s.time).unwrap_or_default();
+    Market::deal(Origin::signed(buyer), venus(), u64::max_value(), time)
+}
+
+#[test]
+fn virgin_deal_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+
+        assert_eq!(Balances::free_balance(ALICE), 900);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(ARTIST), 1080);
+        assert_eq!(Balances::free_balance(MANAGER), 1020);
+        assert_eq!(holder(), ALICE);
+        assert!(Market::on_sell(venus()).is_none());
+        assert!(Market::virgin_sell_out(venus()).is_some());
+    });
+}
+
+#[test]
+fn deal_without_sell_should_fail() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(deal(ALICE), Error::<Test>::NotOnSell);
+    });
+}
+
+#[test]
+fn deal_failed_when_buyer_cant_pay_should_change_nothing() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            2000,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_noop!(deal(ALICE), Error::<Test>::CantPay);
+    });
+}
+
+#[test]
+fn deal_failed_when_buyer_balance_locked_should_change_nothing() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        Balances::set_lock(*b"locktest", &ALICE, 1000, WithdrawReasons::all());
+        assert_noop!(deal(ALICE), Error::<Test>::CantPay);
+    });
+}
+
+#[test]
+fn deal_failed_when_receiver_not_exist_should_change_nothing() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        // the artist binds to an account without any balance
+        assert_ok!(Artists::update_binding(Origin::signed(ARTIST), NOBODY));
+        assert_noop!(deal(ALICE), Error::<Test>::ReceiverNotExist);
+    });
+}
+
+#[test]
+fn deal_failed_when_venus_cant_move_should_change_nothing() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        cirml_artvenuses::HolderOf::<Test>::remove(venus());
+        assert_noop!(deal(ALICE), cirml_artvenuses::Error::<Test>::HolderNotExist);
+    });
+}
+
+#[test]
+fn normal_deal_should_pay_seller() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+        assert_noop!(
+            Market::sell(
+                Origin::signed(ARTIST),
+                venus(),
+                100,
+                SellCurrency::Native,
+                None,
+                None
+            ),
+            Error::<Test>::NotHolderInSell
+        );
+        assert_ok!(Market::sell(
+            Origin::signed(ALICE),
+            venus(),
+            200,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(BOB));
+
+        assert_eq!(Balances::free_balance(BOB), 800);
+        // 2% royalty
+        assert_eq!(Balances::free_balance(ARTIST), 1084);
+        assert_eq!(Balances::free_balance(ALICE), 1096);
+        assert_eq!(Balances::free_balance(MANAGER), 1020);
+        assert_eq!(holder(), BOB);
+    });
+}
+
+#[test]
+fn quote_should_round_down_shares() {
+    let mut schedule = fee_schedule(15, 10);
+    schedule.beneficiaries = vec![(BOB, Percent::from_percent(5))];
+    ExtBuilder::default()
+        .virgin_fee_schedule(schedule)
+        .build()
+        .execute_with(|| {
+            assert_ok!(Market::sell(
+                Origin::signed(ARTIST),
+                venus(),
+                99,
+                SellCurrency::Native,
+                None,
+                None
+            ));
+            let quote = Market::quote_deal(venus()).unwrap();
+            assert_eq!(quote.platform, (MANAGER, 14));
+            assert_eq!(quote.royalty, (ARTIST, 9));
+            assert_eq!(quote.beneficiaries, vec![(BOB, 4)]);
+            // the seller receives the rest
+            assert_eq!(quote.seller, (ARTIST, 72));
+
+            assert_ok!(deal(ALICE));
+            assert_eq!(Balances::free_balance(MANAGER), 1014);
+            assert_eq!(Balances::free_balance(ARTIST), 1081);
+            assert_eq!(Balances::free_balance(BOB), 1004);
+            assert_eq!(Balances::free_balance(ALICE), 901);
+        });
+}
+
+#[test]
+fn set_fee_schedule_should_work() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Market::set_fee_schedule(Origin::signed(MANAGER), false, fee_schedule(10, 10)),
+            BadOrigin
+        );
+        assert_noop!(
+            Market::set_fee_schedule(system::RawOrigin::Root.into(), false, fee_schedule(60, 50)),
+            Error::<Test>::InvalidFeeSchedule
+        );
+        assert_noop!(
+            Market::set_fee_schedule(system::RawOrigin::Root.into(), false, fee_schedule(10, 30)),
+            Error::<Test>::PercentOutOfBounds
+        );
+        assert_noop!(
+            Market::set_fee_schedule(system::RawOrigin::Root.into(), true, fee_schedule(60, 0)),
+            Error::<Test>::PercentOutOfBounds
+        );
+        assert_ok!(Market::set_fee_schedule(
+            system::RawOrigin::Root.into(),
+            false,
+            fee_schedule(10, 10)
+        ));
+        assert_eq!(Market::normal_fee_schedule(), fee_schedule(10, 10));
+        assert_ok!(Market::set_fee_schedule(
+            system::RawOrigin::Root.into(),
+            true,
+            fee_schedule(50, 10)
+        ));
+        assert_eq!(Market::virgin_fee_schedule(), fee_schedule(50, 10));
+    });
+}
+
+#[test]
+fn failed_auction_settlement_should_refund_bidder() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::start_auction(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            5
+        ));
+        assert_noop!(deal(ALICE), Error::<Test>::InBidding);
+        assert_ok!(Market::bid(Origin::signed(ALICE), venus(), 100));
+        assert_ok!(Market::bid(Origin::signed(BOB), venus(), 150));
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::reserved_balance(BOB), 150);
+
+        assert_ok!(Artists::update_binding(Origin::signed(ARTIST), NOBODY));
+        Market::on_finalize(5);
+
+        assert_eq!(Balances::free_balance(BOB), 1000);
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(holder(), ARTIST);
+        assert!(Market::on_sell(venus()).is_none());
+    });
+}
+
+#[test]
+fn auction_should_settle_with_highest_bid() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::start_auction(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            5
+        ));
+        assert_eq!(
+            last_event(),
+            TestEvent::market(RawEvent::AuctionStart(ARTIST, venus(), 100, 5))
+        );
+        assert_ok!(Market::bid(Origin::signed(ALICE), venus(), 100));
+        assert_ok!(Market::bid(Origin::signed(BOB), venus(), 150));
+        assert_eq!(
+            last_event(),
+            TestEvent::market(RawEvent::Bid(BOB, venus(), 150))
+        );
+        Market::on_finalize(5);
+
+        assert_eq!(Balances::free_balance(ALICE), 1000);
+        assert_eq!(Balances::free_balance(BOB), 850);
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(Balances::free_balance(ARTIST), 1120);
+        assert_eq!(Balances::free_balance(MANAGER), 1030);
+        assert_eq!(holder(), BOB);
+    });
+}
+
+#[test]
+fn only_seller_should_cancel_or_update_sell() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_noop!(
+            Market::cancel_sell(Origin::signed(ALICE), venus()),
+            Error::<Test>::NotSeller
+        );
+        assert_noop!(
+            Market::update_price(Origin::signed(ALICE), venus(), 50),
+            Error::<Test>::NotSeller
+        );
+
+        assert_ok!(Market::update_price(Origin::signed(ARTIST), venus(), 200));
+        assert_eq!(
+            last_event(),
+            TestEvent::market(RawEvent::UpdatePrice(ARTIST, venus(), 200))
+        );
+        assert_eq!(Market::on_sell(venus()).unwrap().price, 200);
+
+        assert_ok!(Market::cancel_sell(Origin::signed(ARTIST), venus()));
+        assert_eq!(
+            last_event(),
+            TestEvent::market(RawEvent::CancelSell(ARTIST, venus()))
+        );
+        assert!(Market::on_sell(venus()).is_none());
+    });
+}
+
+#[test]
+fn endings_should_be_bounded_per_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        let other = H256::repeat_byte(2);
+        let third = H256::repeat_byte(3);
+        create_venus(other);
+        create_venus(third);
+        assert_ok!(Market::start_auction(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            5
+        ));
+        assert_ok!(Market::start_raffle(Origin::signed(ARTIST), other, 100, 5));
+        assert_noop!(
+            Market::start_auction(Origin::signed(ARTIST), third, 100, 5),
+            Error::<Test>::TooManyEndings
+        );
+        assert_noop!(
+            Market::start_raffle(Origin::signed(ARTIST), third, 100, 5),
+            Error::<Test>::TooManyEndings
+        );
+        assert_ok!(Market::start_auction(Origin::signed(ARTIST), third, 100, 6));
+    });
+}
+
+#[test]
+fn set_sell_percent_should_respect_bounds() {
+    ExtBuilder::default().build().execute_with(|| {
+        let root = || -> Origin { system::RawOrigin::Root.into() };
+        assert_noop!(
+            Market::set_virgin_sell_percent(Origin::signed(MANAGER), Percent::from_percent(90)),
+            BadOrigin
+        );
+        assert_noop!(
+            Market::set_virgin_sell_percent(root(), Percent::from_percent(40)),
+            Error::<Test>::PercentOutOfBounds
+        );
+        assert_noop!(
+            Market::set_normal_sell_percent(root(), Percent::from_percent(30)),
+            Error::<Test>::PercentOutOfBounds
+        );
+
+        assert_ok!(Market::set_virgin_sell_percent(
+            root(),
+            Percent::from_percent(90)
+        ));
+        assert_eq!(Market::virgin_fee_schedule(), fee_schedule(10, 0));
+        assert_ok!(Market::set_normal_sell_percent(
+            root(),
+            Percent::from_percent(5)
+        ));
+        assert_eq!(Market::normal_fee_schedule(), fee_schedule(0, 5));
+
+        assert_ok!(Market::set_manager(root(), BOB));
+        assert_eq!(Market::manager(), BOB);
+    });
+}
+
+#[test]
+fn accept_offer_should_refund_competing_offers() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Market::make_offer(Origin::signed(ALICE), venus(), 100, 1),
+            Error::<Test>::InvalidExpire
+        );
+        assert_ok!(Market::make_offer(Origin::signed(ALICE), venus(), 100, 10));
+        assert_ok!(Market::make_offer(Origin::signed(BOB), venus(), 200, 10));
+        assert_eq!(Balances::reserved_balance(ALICE), 100);
+        assert_eq!(Balances::reserved_balance(BOB), 200);
+
+        assert_noop!(
+            Market::accept_offer(Origin::signed(ALICE), venus(), BOB),
+            Error::<Test>::NotHolder
+        );
+        assert_ok!(Market::accept_offer(Origin::signed(ARTIST), venus(), BOB));
+
+        assert_eq!(holder(), BOB);
+        assert_eq!(Balances::free_balance(BOB), 800);
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+        assert_eq!(Balances::free_balance(ALICE), 1000);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(ARTIST), 1160);
+        assert!(Market::offer_list(venus()).is_empty());
+    });
+}
+
+#[test]
+fn offer_should_expire_at_its_expire_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::make_offer(Origin::signed(ALICE), venus(), 100, 3));
+        System::set_block_number(3);
+        assert_noop!(
+            Market::accept_offer(Origin::signed(ARTIST), venus(), ALICE),
+            Error::<Test>::OfferExpired
+        );
+    });
+}
+
+#[test]
+fn offers_should_be_bounded() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Market::make_offer(Origin::signed(ALICE), venus(), 5, 10),
+            Error::<Test>::OfferTooLow
+        );
+        assert_ok!(Market::make_offer(Origin::signed(ALICE), venus(), 100, 10));
+        assert_ok!(Market::make_offer(Origin::signed(BOB), venus(), 100, 10));
+        assert_noop!(
+            Market::make_offer(Origin::signed(MANAGER), venus(), 100, 10),
+            Error::<Test>::TooManyOffers
+        );
+        // an offer could still be replaced
+        assert_ok!(Market::make_offer(Origin::signed(ALICE), venus(), 150, 10));
+        assert_eq!(Market::offer_counts(venus()), 2);
+
+        assert_ok!(Market::withdraw_offer(Origin::signed(BOB), venus()));
+        assert_ok!(Market::make_offer(
+            Origin::signed(MANAGER),
+            venus(),
+            100,
+            10
+        ));
+        assert_ok!(Market::accept_offer(Origin::signed(ARTIST), venus(), ALICE));
+        assert_eq!(Market::offer_counts(venus()), 0);
+        assert_eq!(Balances::reserved_balance(MANAGER), 0);
+    });
+}
+
+#[test]
+fn dutch_auction_should_deal_at_current_price() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Market::start_dutch_auction(
+                Origin::signed(ARTIST),
+                venus(),
+                100,
+                200,
+                PriceDecay::Linear(10),
+                SellCurrency::Native
+            ),
+            Error::<Test>::InvalidDutchAuction
+        );
+        assert_noop!(
+            Market::start_dutch_auction(
+                Origin::signed(ARTIST),
+                venus(),
+                500,
+                200,
+                PriceDecay::Step(0, 10),
+                SellCurrency::Native
+            ),
+            Error::<Test>::InvalidDutchAuction
+        );
+        assert_ok!(Market::start_dutch_auction(
+            Origin::signed(ARTIST),
+            venus(),
+            500,
+            200,
+            PriceDecay::Step(2, 50),
+            SellCurrency::Native
+        ));
+        let sell_info = Market::on_sell(venus()).unwrap();
+        assert_eq!(Market::current_price(venus(), &sell_info), 500);
+
+        System::set_block_number(6);
+        assert_eq!(Market::current_price(venus(), &sell_info), 400);
+        assert_eq!(Market::quote_deal(venus()).unwrap().price, 400);
+        System::set_block_number(100);
+        assert_eq!(Market::on_sell_list()[0].1.price, 200);
+
+        assert_ok!(deal(ALICE));
+        assert_eq!(holder(), ALICE);
+        assert_eq!(Balances::free_balance(ALICE), 800);
+        assert_eq!(Market::dutch_auctions(venus()), None);
+    });
+}
+
+#[test]
+fn seller_should_not_deal_own_venus() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_noop!(deal(ARTIST), Error::<Test>::DealOwnVenus);
+        assert_ok!(deal(ALICE));
+
+        assert_ok!(Market::sell(
+            Origin::signed(ALICE),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_noop!(deal(ALICE), Error::<Test>::DealOwnVenus);
+        assert_eq!(Market::artist_sales(0).count, 1);
+    });
+}
+
+#[test]
+fn deal_should_respect_max_price_and_sell_time() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        System::set_block_number(2);
+        assert_ok!(Market::update_price(Origin::signed(ARTIST), venus(), 300));
+        assert_noop!(
+            Market::deal(Origin::signed(ALICE), venus(), 100, 1),
+            Error::<Test>::SellChanged
+        );
+        assert_noop!(
+            Market::deal(Origin::signed(ALICE), venus(), 100, 2),
+            Error::<Test>::PriceTooHigh
+        );
+        assert_ok!(Market::deal(Origin::signed(ALICE), venus(), 300, 2));
+        assert_eq!(holder(), ALICE);
+    });
+}
+
+#[test]
+fn private_sell_should_only_deal_with_designated_buyer() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            Some(BOB),
+            None
+        ));
+        assert_noop!(deal(ALICE), Error::<Test>::NotDesignatedBuyer);
+        assert_ok!(deal(BOB));
+        assert_eq!(holder(), BOB);
+    });
+}
+
+#[test]
+fn expired_sells_should_be_removed_in_bounded_blocks() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Market::sell(
+                Origin::signed(ARTIST),
+                venus(),
+                100,
+                SellCurrency::Native,
+                None,
+                Some(1)
+            ),
+            Error::<Test>::InvalidExpire
+        );
+        let venuses = vec![venus(), H256::repeat_byte(2), H256::repeat_byte(3)];
+        for id in venuses.iter() {
+            if *id != venus() {
+                create_venus(*id);
+            }
+            assert_ok!(Market::sell(
+                Origin::signed(ARTIST),
+                *id,
+                100,
+                SellCurrency::Native,
+                None,
+                Some(3)
+            ));
+        }
+
+        System::set_block_number(3);
+        assert_noop!(deal(ALICE), Error::<Test>::SellExpired);
+        assert!(Market::on_sell_list().is_empty());
+        assert_eq!(Market::quote_deal(venus()), None);
+
+        Market::on_initialize(3);
+        assert_eq!(
+            venuses
+                .iter()
+                .filter(|id| Market::on_sell(*id).is_some())
+                .count(),
+            1
+        );
+        assert_eq!(Market::expire_cursor(), 3);
+
+        System::set_block_number(4);
+        Market::on_initialize(4);
+        assert!(venuses.iter().all(|id| Market::on_sell(*id).is_none()));
+        assert_eq!(Market::expire_cursor(), 5);
+    });
+}
+
+#[test]
+fn lot_should_deal_all_venuses_together() {
+    ExtBuilder::default().build().execute_with(|| {
+        let other = H256::repeat_byte(2);
+        create_venus(other);
+        assert_noop!(
+            Market::sell_lot(
+                Origin::signed(ARTIST),
+                vec![(venus(), 100), (venus(), 100)],
+                SellCurrency::Native
+            ),
+            Error::<Test>::InvalidLot
+        );
+        assert_noop!(
+            Market::sell_lot(
+                Origin::signed(ALICE),
+                vec![(venus(), 100), (other, 100)],
+                SellCurrency::Native
+            ),
+            Error::<Test>::NotCreaterInVirginSell
+        );
+        assert_ok!(Market::sell_lot(
+            Origin::signed(ARTIST),
+            vec![(venus(), 100), (other, 200)],
+            SellCurrency::Native
+        ));
+        assert_noop!(
+            Market::sell(
+                Origin::signed(ARTIST),
+                other,
+                100,
+                SellCurrency::Native,
+                None,
+                None
+            ),
+            Error::<Test>::InLot
+        );
+        assert_noop!(
+            Market::deal_lot(Origin::signed(ALICE), 0, 299, 1),
+            Error::<Test>::PriceTooHigh
+        );
+
+        assert_noop!(
+            Market::deal_lot(Origin::signed(ARTIST), 0, 300, 1),
+            Error::<Test>::DealOwnVenus
+        );
+        assert_ok!(Market::deal_lot(Origin::signed(ALICE), 0, 300, 1));
+        assert_eq!(holder(), ALICE);
+        assert_eq!(Artvenuses::holder_for(other).unwrap(), ALICE);
+        assert_eq!(Balances::free_balance(ALICE), 700);
+        assert_eq!(Balances::free_balance(ARTIST), 1240);
+        assert_eq!(Balances::free_balance(MANAGER), 1060);
+        assert!(Market::lots(0).is_none());
+        assert!(Market::lot_of(other).is_none());
+    });
+}
+
+#[test]
+fn swap_should_charge_royalties_on_valuation() {
+    ExtBuilder::default().build().execute_with(|| {
+        let other = H256::repeat_byte(2);
+        create_venus(other);
+        assert_noop!(
+            Market::propose_swap(Origin::signed(ARTIST), venus(), other, 0, 100),
+            Error::<Test>::InvalidSwap
+        );
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+        assert_noop!(
+            Market::propose_swap(Origin::signed(ALICE), venus(), other, 0, 100),
+            Error::<Test>::VirginInSwap
+        );
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            other,
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(Market::deal(Origin::signed(BOB), other, 100, 1));
+
+        // neither venus could be valued under its last sale price
+        assert_noop!(
+            Market::propose_swap(Origin::signed(ALICE), venus(), other, 50, 0),
+            Error::<Test>::ValuationTooLow
+        );
+        assert_noop!(
+            Market::propose_swap(Origin::signed(ALICE), venus(), other, 10, 99),
+            Error::<Test>::ValuationTooLow
+        );
+        // 2% royalty of 100 for `venus` is reserved with the top-up
+        assert_ok!(Market::propose_swap(
+            Origin::signed(ALICE),
+            venus(),
+            other,
+            50,
+            100
+        ));
+        assert_eq!(Balances::reserved_balance(ALICE), 52);
+        assert_noop!(
+            Market::accept_swap(Origin::signed(ALICE), 0),
+            Error::<Test>::NotHolder
+        );
+        assert_ok!(Market::accept_swap(Origin::signed(BOB), 0));
+
+        assert_eq!(holder(), BOB);
+        assert_eq!(Artvenuses::holder_for(other).unwrap(), ALICE);
+        assert_eq!(Balances::free_balance(ALICE), 848);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        // 2% royalty of 150 for `other`
+        assert_eq!(Balances::free_balance(BOB), 947);
+        assert_eq!(Balances::free_balance(ARTIST), 1165);
+        assert!(Market::swaps(0).is_none());
+        assert!(Market::swaps_of(venus(), 0).is_none());
+    });
+}
+
+#[test]
+fn venus_sold_in_stable_should_not_be_swapped() {
+    ExtBuilder::default().build().execute_with(|| {
+        let other = H256::repeat_byte(2);
+        create_venus(other);
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Stable,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            other,
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(Market::deal(Origin::signed(BOB), other, 100, 1));
+
+        assert_noop!(
+            Market::propose_swap(Origin::signed(ALICE), venus(), other, 0, 100),
+            Error::<Test>::StableInSwap
+        );
+        assert_noop!(
+            Market::propose_swap(Origin::signed(BOB), other, venus(), 0, 100),
+            Error::<Test>::StableInSwap
+        );
+    });
+}
+
+#[test]
+fn deal_should_settle_in_sell_currency() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Stable,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+
+        assert_eq!(StableBalances::free_balance(ALICE), 900);
+        assert_eq!(StableBalances::free_balance(ARTIST), 1080);
+        assert_eq!(StableBalances::free_balance(MANAGER), 1020);
+        assert_eq!(Balances::free_balance(ALICE), 1000);
+        assert_eq!(holder(), ALICE);
+
+        // BOB has no stable token
+        assert_ok!(Market::sell(
+            Origin::signed(ALICE),
+            venus(),
+            100,
+            SellCurrency::Stable,
+            None,
+            None
+        ));
+        assert_noop!(deal(BOB), Error::<Test>::CantPay);
+    });
+}
+
+#[test]
+fn sell_should_check_receivers_hold_sell_currency() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(BOB));
+
+        // BOB has no stable token to receive the deal
+        assert_noop!(
+            Market::sell(
+                Origin::signed(BOB),
+                venus(),
+                100,
+                SellCurrency::Stable,
+                None,
+                None
+            ),
+            Error::<Test>::ReceiverNotExist
+        );
+        assert_noop!(
+            Market::sell_lot(
+                Origin::signed(BOB),
+                vec![(venus(), 100)],
+                SellCurrency::Stable
+            ),
+            Error::<Test>::ReceiverNotExist
+        );
+        assert_ok!(Market::sell(
+            Origin::signed(BOB),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn deal_should_record_sale() {
+    ExtBuilder::default().build().execute_with(|| {
+        let artist_id = Artvenuses::get_artvenus(venus()).unwrap().origin;
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+        System::set_block_number(2);
+        assert_ok!(Market::sell(
+            Origin::signed(ALICE),
+            venus(),
+            200,
+            SellCurrency::Stable,
+            None,
+            None
+        ));
+        assert_ok!(Market::deal(Origin::signed(MANAGER), venus(), 200, 2));
+
+        assert_eq!(
+            Market::last_sale(venus()),
+            Some(SaleRecord {
+                price: 200,
+                currency: SellCurrency::Stable,
+                block: 2,
+                buyer: MANAGER,
+                seller: ALICE,
+                is_virgin_sell: false,
+            })
+        );
+        assert_eq!(
+            Market::artist_sales(artist_id),
+            SalesStats {
+                volume: 100,
+                stable_volume: 200,
+                count: 2,
+            }
+        );
+    });
+}
+
+#[test]
+fn paused_market_should_reject_calls() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_noop!(Market::set_paused(Origin::signed(ALICE), true), BadOrigin);
+        assert_ok!(Market::set_paused(Origin::signed(MANAGER), true));
+        assert_noop!(
+            Market::update_price(Origin::signed(ARTIST), venus(), 200),
+            Error::<Test>::MarketPaused
+        );
+        assert_noop!(deal(ALICE), Error::<Test>::MarketPaused);
+        // the sell order could still be canceled
+        assert_ok!(Market::cancel_sell(Origin::signed(ARTIST), venus()));
+        assert_noop!(
+            Market::sell(
+                Origin::signed(ARTIST),
+                venus(),
+                100,
+                SellCurrency::Native,
+                None,
+                None
+            ),
+            Error::<Test>::MarketPaused
+        );
+
+        assert_ok!(Market::set_paused(system::RawOrigin::Root.into(), false));
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+    });
+}
+
+#[test]
+fn check_market_paused_should_only_pass_cancel_and_admin_calls() {
+    ExtBuilder::default().build().execute_with(|| {
+        let check = CheckMarketPaused::<Test>::new();
+        let info = DispatchInfo::default();
+        let sell = Call::Market(crate::Call::sell(
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None,
+        ));
+        let cancel = Call::Market(crate::Call::cancel_sell(venus()));
+        let withdraw = Call::Market(crate::Call::withdraw_offer(venus()));
+        let resume = Call::Market(crate::Call::set_paused(false));
+        let gallery = Call::Market(crate::Call::set_gallery(BOB, true));
+        let cooldown = Call::Market(crate::Call::set_resale_cooldown(10));
+        assert_ok!(check.validate(&ARTIST, &sell, &info, 0));
+
+        assert_ok!(Market::set_paused(Origin::signed(MANAGER), true));
+        assert_eq!(
+            check.validate(&ARTIST, &sell, &info, 0),
+            InvalidTransaction::Custom(MARKET_PAUSED).into()
+        );
+        assert_ok!(check.validate(&ARTIST, &cancel, &info, 0));
+        assert_ok!(check.validate(&ALICE, &withdraw, &info, 0));
+        assert_ok!(check.validate(&MANAGER, &gallery, &info, 0));
+        assert_ok!(check.validate(&MANAGER, &cooldown, &info, 0));
+        assert_ok!(check.validate(&MANAGER, &resume, &info, 0));
+    });
+}
+
+#[test]
+fn deal_for_should_move_venus_to_recipient() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(Market::deal_for(
+            Origin::signed(ALICE),
+            venus(),
+            BOB,
+            100,
+            1
+        ));
+
+        assert_eq!(holder(), BOB);
+        assert_eq!(Balances::free_balance(ALICE), 900);
+        assert_eq!(Balances::free_balance(BOB), 1000);
+        assert_eq!(Balances::free_balance(ARTIST), 1080);
+    });
+}
+
+#[test]
+fn consignee_should_sell_for_commission() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_noop!(
+            Market::consign(
+                Origin::signed(ARTIST),
+                venus(),
+                BOB,
+                Percent::from_percent(10)
+            ),
+            Error::<Test>::NotGallery
+        );
+        assert_ok!(Market::set_gallery(Origin::signed(MANAGER), BOB, true));
+        assert_noop!(
+            Market::consign(
+                Origin::signed(ARTIST),
+                venus(),
+                BOB,
+                Percent::from_percent(90)
+            ),
+            Error::<Test>::InvalidCommission
+        );
+        assert_ok!(Market::consign(
+            Origin::signed(ARTIST),
+            venus(),
+            BOB,
+            Percent::from_percent(10)
+        ));
+        assert_ok!(Market::sell(
+            Origin::signed(BOB),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+
+        assert_eq!(holder(), ALICE);
+        assert_eq!(Balances::free_balance(BOB), 1010);
+        assert_eq!(Balances::free_balance(ARTIST), 1070);
+        assert_eq!(Balances::free_balance(MANAGER), 1020);
+        assert!(Market::consignments(venus()).is_none());
+    });
+}
+
+#[test]
+fn holder_should_sell_consigned_venus_without_commission() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::set_gallery(Origin::signed(MANAGER), BOB, true));
+        assert_ok!(Market::consign(
+            Origin::signed(ARTIST),
+            venus(),
+            BOB,
+            Percent::from_percent(10)
+        ));
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+
+        assert_eq!(holder(), ALICE);
+        assert_eq!(Balances::free_balance(BOB), 1000);
+        assert_eq!(Balances::free_balance(ARTIST), 1080);
+        assert_eq!(Balances::free_balance(MANAGER), 1020);
+    });
+}
+
+#[test]
+fn raffle_should_deal_with_drawn_entrant() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::start_raffle(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            5
+        ));
+        assert_noop!(deal(ALICE), Error::<Test>::InBidding);
+        assert_noop!(
+            Market::enter_raffle(Origin::signed(ARTIST), venus()),
+            Error::<Test>::EnterOwnRaffle
+        );
+        assert_ok!(Market::enter_raffle(Origin::signed(ALICE), venus()));
+        assert_noop!(
+            Market::enter_raffle(Origin::signed(ALICE), venus()),
+            Error::<Test>::AlreadyEntered
+        );
+        assert_ok!(Market::enter_raffle(Origin::signed(BOB), venus()));
+        assert_noop!(
+            Market::enter_raffle(Origin::signed(MANAGER), venus()),
+            Error::<Test>::RaffleFull
+        );
+        assert_eq!(Balances::reserved_balance(ALICE), 100);
+        assert_eq!(Balances::reserved_balance(BOB), 100);
+        // the settlement and refunds are weighed ahead
+        assert!(Market::on_initialize(5) > Market::on_initialize(4));
+
+        Market::on_finalize(5);
+
+        let winner = holder();
+        let loser = if winner == ALICE { BOB } else { ALICE };
+        assert!(winner == ALICE || winner == BOB);
+        assert_eq!(Balances::free_balance(winner), 900);
+        assert_eq!(Balances::reserved_balance(winner), 0);
+        assert_eq!(Balances::free_balance(loser), 1000);
+        assert_eq!(Balances::reserved_balance(loser), 0);
+        assert_eq!(Balances::free_balance(ARTIST), 1080);
+        assert_eq!(Balances::free_balance(MANAGER), 1020);
+        assert!(Market::on_sell(venus()).is_none());
+        assert!(Market::raffles(venus()).is_none());
+    });
+}
+
+#[test]
+fn raffle_should_close_entries_at_end_block() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::start_raffle(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            5
+        ));
+        assert_ok!(Market::enter_raffle(Origin::signed(ALICE), venus()));
+        System::set_block_number(5);
+        assert_noop!(
+            Market::enter_raffle(Origin::signed(BOB), venus()),
+            Error::<Test>::RaffleEnded
+        );
+
+        // the entrants are refunded without the sell order
+        OnSell::<Test>::remove(venus());
+        Market::on_finalize(5);
+        assert_eq!(holder(), ARTIST);
+        assert_eq!(Balances::free_balance(ALICE), 1000);
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert!(Market::raffles(venus()).is_none());
+    });
+}
+
+#[test]
+fn presale_should_limit_virgin_deals_to_allowlist() {
+    ExtBuilder::default().build().execute_with(|| {
+        let other = H256::repeat_byte(2);
+        create_venus(other);
+        assert_noop!(
+            Market::set_presale(
+                Origin::signed(ARTIST),
+                Allowlist::List(vec![ALICE]),
+                5,
+                5,
+                1
+            ),
+            Error::<Test>::InvalidPresale
+        );
+        assert_ok!(Market::set_presale(
+            Origin::signed(ARTIST),
+            Allowlist::List(vec![ALICE]),
+            1,
+            5,
+            1
+        ));
+        for id in vec![venus(), other] {
+            assert_ok!(Market::sell(
+                Origin::signed(ARTIST),
+                id,
+                100,
+                SellCurrency::Native,
+                None,
+                None
+            ));
+        }
+
+        assert_noop!(deal(BOB), Error::<Test>::NotInAllowlist);
+        assert_ok!(deal(ALICE));
+        assert_noop!(
+            Market::deal(Origin::signed(ALICE), other, 100, 1),
+            Error::<Test>::PresaleLimitReached
+        );
+        assert_eq!(Market::presale_purchases(0, ALICE), 1);
+
+        System::set_block_number(5);
+        assert_ok!(Market::deal(Origin::signed(BOB), other, 100, 1));
+        assert_eq!(Artvenuses::holder_for(other).unwrap(), BOB);
+    });
+}
+
+#[test]
+fn presale_should_refuse_others_before_start_and_in_lot() {
+    ExtBuilder::default().build().execute_with(|| {
+        let other = H256::repeat_byte(2);
+        create_venus(other);
+        assert_ok!(Market::set_presale(
+            Origin::signed(ARTIST),
+            Allowlist::List(vec![ALICE]),
+            3,
+            5,
+            1
+        ));
+        assert_ok!(Market::sell_lot(
+            Origin::signed(ARTIST),
+            vec![(venus(), 100), (other, 100)],
+            SellCurrency::Native
+        ));
+
+        assert_noop!(
+            Market::deal_lot(Origin::signed(BOB), 0, 200, 1),
+            Error::<Test>::NotInAllowlist
+        );
+        assert_noop!(
+            Market::deal_lot(Origin::signed(ALICE), 0, 200, 1),
+            Error::<Test>::PresaleNotStarted
+        );
+        System::set_block_number(3);
+        assert_noop!(
+            Market::deal_lot(Origin::signed(BOB), 0, 200, 1),
+            Error::<Test>::NotInAllowlist
+        );
+        // both venuses are counted in the phase
+        assert_noop!(
+            Market::deal_lot(Origin::signed(ALICE), 0, 200, 1),
+            Error::<Test>::PresaleLimitReached
+        );
+        System::set_block_number(5);
+        assert_ok!(Market::deal_lot(Origin::signed(BOB), 0, 200, 1));
+        assert_eq!(holder(), BOB);
+    });
+}
+
+#[test]
+fn presale_should_allow_accounts_proved_in_merkle_root() {
+    ExtBuilder::default().build().execute_with(|| {
+        let alice = BlakeTwo256::hash_of(&ALICE);
+        let bob = BlakeTwo256::hash_of(&BOB);
+        let root = if alice <= bob {
+            BlakeTwo256::hash_of(&(alice, bob))
+        } else {
+            BlakeTwo256::hash_of(&(bob, alice))
+        };
+        assert_ok!(Market::set_presale(
+            Origin::signed(ARTIST),
+            Allowlist::MerkleRoot(root),
+            1,
+            5,
+            1
+        ));
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+
+        assert_noop!(
+            Market::join_presale(Origin::signed(ALICE), 0, vec![alice]),
+            Error::<Test>::InvalidMerkleProof
+        );
+        assert_noop!(deal(ALICE), Error::<Test>::NotInAllowlist);
+        assert_ok!(Market::join_presale(Origin::signed(ALICE), 0, vec![bob]));
+        assert_ok!(deal(ALICE));
+        assert_eq!(holder(), ALICE);
+    });
+}
+
+#[test]
+fn resale_should_wait_for_cooldown() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::set_resale_cooldown(Origin::signed(MANAGER), 5));
+        assert_ok!(Market::set_artist_cooldown(
+            Origin::signed(ARTIST),
+            Some(10)
+        ));
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+        assert_eq!(Artvenuses::acquired_at(venus()), Some(1));
+
+        let resell = || {
+            Market::sell(
+                Origin::signed(ALICE),
+                venus(),
+                100,
+                SellCurrency::Native,
+                None,
+                None,
+            )
+        };
+        assert_ok!(Market::make_offer(Origin::signed(BOB), venus(), 200, 20));
+        System::set_block_number(6);
+        assert_noop!(resell(), Error::<Test>::InCooldown);
+        assert_noop!(
+            Market::accept_offer(Origin::signed(ALICE), venus(), BOB),
+            Error::<Test>::InCooldown
+        );
+        System::set_block_number(11);
+        assert_ok!(resell());
+    });
+}
+
+#[test]
+fn transfer_should_refuse_listed_venus() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+        assert_noop!(
+            Artvenuses::transfer(Origin::signed(BOB), venus(), ALICE),
+            cirml_artvenuses::Error::<Test>::NotHolder
+        );
+        assert_ok!(Market::sell(
+            Origin::signed(ALICE),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_noop!(
+            Artvenuses::transfer(Origin::signed(ALICE), venus(), BOB),
+            cirml_artvenuses::Error::<Test>::ArtvenusListed
+        );
+        assert_ok!(Market::cancel_sell(Origin::signed(ALICE), venus()));
+
+        assert_ok!(Artvenuses::transfer(Origin::signed(ALICE), venus(), BOB));
+        assert_eq!(holder(), BOB);
+        assert_eq!(Artvenuses::holder_artvenuses(BOB, 0), Some(venus()));
+    });
+}
+
+#[test]
+fn artist_should_not_transfer_unsold_venus() {
+    ExtBuilder::default().build().execute_with(|| {
+        // the virgin sell fees could not be skipped by a transfer to another account
+        assert_noop!(
+            Artvenuses::transfer(Origin::signed(ARTIST), venus(), BOB),
+            cirml_artvenuses::Error::<Test>::ArtvenusNotSold
+        );
+        assert_ok!(Artvenuses::approve(
+            Origin::signed(ARTIST),
+            venus(),
+            Some(BOB)
+        ));
+        assert_noop!(
+            Artvenuses::transfer_from(Origin::signed(BOB), venus(), BOB),
+            cirml_artvenuses::Error::<Test>::ArtvenusNotSold
+        );
+        assert!(Market::virgin_sell_out(venus()).is_none());
+
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+        assert_eq!(Balances::free_balance(MANAGER), 1020);
+        assert_ok!(Artvenuses::transfer(Origin::signed(ALICE), venus(), BOB));
+        assert_eq!(holder(), BOB);
+    });
+}
+
+#[test]
+fn transfer_from_should_honor_approvals() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+        assert_noop!(
+            Artvenuses::transfer_from(Origin::signed(BOB), venus(), MANAGER),
+            cirml_artvenuses::Error::<Test>::NotApproved
+        );
+        assert_ok!(Artvenuses::approve(
+            Origin::signed(ALICE),
+            venus(),
+            Some(BOB)
+        ));
+        assert_ok!(Artvenuses::transfer_from(
+            Origin::signed(BOB),
+            venus(),
+            MANAGER
+        ));
+        assert_eq!(holder(), MANAGER);
+        assert!(Artvenuses::approvals(venus()).is_none());
+        assert_noop!(
+            Artvenuses::transfer_from(Origin::signed(BOB), venus(), BOB),
+            cirml_artvenuses::Error::<Test>::NotApproved
+        );
+
+        assert_ok!(Artvenuses::set_approval_for_all(
+            Origin::signed(MANAGER),
+            BOB,
+            true
+        ));
+        assert_ok!(Artvenuses::transfer_from(Origin::signed(BOB), venus(), BOB));
+        assert_eq!(holder(), BOB);
+    });
+}
+
+#[test]
+fn provenance_should_record_every_holder() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        System::set_block_number(2);
+        assert_ok!(Market::deal_for(
+            Origin::signed(ALICE),
+            venus(),
+            BOB,
+            100,
+            1
+        ));
+        System::set_block_number(3);
+        assert_ok!(Artvenuses::transfer(Origin::signed(BOB), venus(), ALICE));
+
+        let provenance: Vec<_> = Artvenuses::provenance(venus())
+            .into_iter()
+            .map(|r| {
+                (
+                    r.holder,
+                    r.block,
+                    r.acquisition,
+                    r.price.map(|p| (p, r.currency)),
+                )
+            })
+            .collect();
+        assert_eq!(
+            provenance,
+            vec![
+                (ARTIST, 1, AcquisitionType::Created, None),
+                (
+                    BOB,
+                    2,
+                    AcquisitionType::Gifted,
+                    Some((100, SellCurrency::Native))
+                ),
+                (ALICE, 3, AcquisitionType::Transferred, None),
+            ]
+        );
+    });
+}
+
+#[test]
+fn burn_should_clean_up_and_leave_tombstone() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(Market::make_offer(Origin::signed(ALICE), venus(), 50, 10));
+        assert_noop!(
+            Artvenuses::burn(Origin::signed(ALICE), venus()),
+            cirml_artvenuses::Error::<Test>::NotArtist
+        );
+
+        assert_ok!(Artvenuses::burn(Origin::signed(ARTIST), venus()));
+        assert!(Artvenuses::artvenus_infos(venus()).is_none());
+        assert!(Artvenuses::holder_of(venus()).is_none());
+        assert!(Artvenuses::holder_artvenuses(ARTIST, 0).is_none());
+        assert!(Artvenuses::artist_artvenuses(0, 0).is_none());
+        assert!(Market::on_sell(venus()).is_none());
+        assert!(Market::offers(venus(), ALICE).is_none());
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_noop!(
+            Artvenuses::create_artvenus(Origin::signed(ARTIST), venus(), text("venus"), text("")),
+            cirml_artvenuses::Error::<Test>::ArtvenusBurned
+        );
+    });
+}
+
+#[test]
+fn only_holder_should_burn_after_sale() {
+    ExtBuilder::default().build().execute_with(|| {
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            venus(),
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(deal(ALICE));
+        let other = H256::repeat_byte(2);
+        create_venus(other);
+        assert_ok!(Market::sell(
+            Origin::signed(ARTIST),
+            other,
+            100,
+            SellCurrency::Native,
+            None,
+            None
+        ));
+        assert_ok!(Market::deal(Origin::signed(BOB), other, 100, 1));
+        assert_ok!(Market::propose_swap(
+            Origin::signed(ALICE),
+            venus(),
+            other,
+            50,
+            100
+        ));
+        assert_eq!(Balances::reserved_balance(ALICE), 52);
+        assert!(Market::swaps_of(other, 0).is_some());
+
+        assert_noop!(
+            Artvenuses::burn(Origin::signed(ARTIST), venus()),
+            cirml_artvenuses::Error::<Test>::NotHolder
+        );
+        assert_ok!(Artvenuses::burn(Origin::signed(ALICE), venus()));
+        assert!(Artvenuses::burned(venus()).is_some());
+        // the swap of the burned venus is dropped
+        assert!(Market::swaps(0).is_none());
+        assert!(Market::swaps_of(other, 0).is_none());
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+    });
+}
@@ -1,6 +1,27 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use codec::{Decode, Encode};
+use sp_runtime::RuntimeDebug;
 use sp_std::prelude::Vec;
 
 pub type ArtistId = u32;
 pub type Text = Vec<u8>;
+
+/// The currencies to settle a deal in.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum SellCurrency {
+    /// the default balances instance
+    Native,
+    /// the `StableCurrency` of the market, e.g. a stable settlement token
+    Stable,
+}
+
+impl Default for SellCurrency {
+    fn default() -> Self {
+        SellCurrency::Native
+    }
+}
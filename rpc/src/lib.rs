@@ -73,9 +73,15 @@ where
     <Client<BE, E, Block, RA> as ProvideRuntimeApi<Block>>::Api:
         cirml_artists_runtime_api::ArtistsApi<Block, AccountId>,
     <Client<BE, E, Block, RA> as ProvideRuntimeApi<Block>>::Api:
-        cirml_artvenuses_runtime_api::ArtvenusesApi<Block, AccountId, ArtvenusId>,
+        cirml_artvenuses_runtime_api::ArtvenusesApi<
+            Block,
+            AccountId,
+            ArtvenusId,
+            BlockNumber,
+            Balance,
+        >,
     <Client<BE, E, Block, RA> as ProvideRuntimeApi<Block>>::Api:
-        cirml_market_runtime_api::MarketApi<Block, ArtvenusId, Balance, BlockNumber>,
+        cirml_market_runtime_api::MarketApi<Block, AccountId, ArtvenusId, Balance, BlockNumber>,
     <<Client<BE, E, Block, RA> as ProvideRuntimeApi<Block>>::Api as sp_api::ApiErrorExt>::Error:
         fmt::Debug,
     P: TransactionPool + 'static,
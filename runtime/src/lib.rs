@@ -8,6 +8,7 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use frame_support::traits::StorageMapShim;
 use pallet_grandpa::fg_primitives;
 use pallet_grandpa::{AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
 use sp_api::impl_runtime_apis;
@@ -44,10 +45,15 @@ pub use frame_support::{
 pub use pallet_timestamp::Call as TimestampCall;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Percent, Permill};
 
-pub use cirml_market::OnSellState;
-pub type OnSellInfo = cirml_market::OnSellInfo<Balance, BlockNumber>;
+pub use cirml_market::{FeeSchedule, OnSellState, SellCurrency};
+pub type OnSellInfo = cirml_market::OnSellInfo<AccountId, Balance, BlockNumber>;
+pub type DealQuote = cirml_market::DealQuote<AccountId, Balance>;
+pub type OfferInfo = cirml_market::OfferInfo<Balance, BlockNumber>;
+pub type SaleRecord = cirml_market::SaleRecord<AccountId, Balance, BlockNumber>;
+pub type SalesStats = cirml_market::SalesStats<Balance>;
+pub type ProvenanceRecord = cirml_artvenuses::ProvenanceRecord<AccountId, BlockNumber, Balance>;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -106,10 +112,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("cryptoindus"),
     impl_name: create_runtime_str!("cryptoindus"),
     authoring_version: 1,
-    spec_version: 1,
+    spec_version: 2,
     impl_version: 1,
     apis: RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
 };
 
 pub const MILLISECS_PER_BLOCK: u64 = 6000;
@@ -236,6 +242,7 @@ impl cirml_artists::Trait for Runtime {
 impl cirml_artvenuses::Trait for Runtime {
     type Hash = ArtvenusId;
     type Event = Event;
+    type Market = Market;
 }
 
 parameter_types! {
@@ -252,8 +259,44 @@ impl cirml_balances::Trait for Runtime {
     type AccountStore = System;
 }
 
+/// The stable token to sell in the market.
+impl cirml_balances::Trait<cirml_balances::Instance1> for Runtime {
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = Event;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = StorageMapShim<
+        cirml_balances::Account<Runtime, cirml_balances::Instance1>,
+        frame_system::CallOnCreatedAccount<Runtime>,
+        frame_system::CallKillAccount<Runtime>,
+        AccountId,
+        cirml_balances::AccountData<Balance>,
+    >;
+}
+
+parameter_types! {
+    pub const MinVirginSellPercent: Percent = Percent::from_percent(50);
+    pub const MaxNormalSellPercent: Percent = Percent::from_percent(20);
+    pub const MaxExpiredSellsPerBlock: u32 = 100;
+    pub const MaxRaffleEntrants: u32 = 1000;
+    pub const MinSwapValuation: Balance = 500;
+    pub const MaxEndingsPerBlock: u32 = 50;
+    pub const MinOfferPrice: Balance = 500;
+    pub const MaxOffersPerVenus: u32 = 100;
+}
+
 impl cirml_market::Trait for Runtime {
     type Event = Event;
+    type StableCurrency = StableBalances;
+    type MinVirginSellPercent = MinVirginSellPercent;
+    type MaxNormalSellPercent = MaxNormalSellPercent;
+    type MaxExpiredSellsPerBlock = MaxExpiredSellsPerBlock;
+    type Randomness = RandomnessCollectiveFlip;
+    type MaxRaffleEntrants = MaxRaffleEntrants;
+    type MinSwapValuation = MinSwapValuation;
+    type MaxEndingsPerBlock = MaxEndingsPerBlock;
+    type MinOfferPrice = MinOfferPrice;
+    type MaxOffersPerVenus = MaxOffersPerVenus;
 }
 
 parameter_types! {
@@ -288,8 +331,9 @@ construct_runtime!(
         Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>},
 
         Artists: cirml_artists::{Module, Call, Storage, Event<T>},
-        Artvenuses: cirml_artvenuses::{Module, Call, Storage, Event<T>},
+        Artvenuses: cirml_artvenuses::{Module, Call, Storage, Config, Event<T>},
         Balances: cirml_balances::{Module, Call, Storage, Config<T>, Event<T>},
+        StableBalances: cirml_balances::<Instance1>::{Module, Call, Storage, Config<T>, Event<T>},
         Market: cirml_market::{Module, Call, Storage, Config<T>, Event<T>},
     }
 );
@@ -313,6 +357,7 @@ pub type SignedExtra = (
     frame_system::CheckNonce<Runtime>,
     frame_system::CheckWeight<Runtime>,
     pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+    cirml_market::CheckMarketPaused<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -466,6 +511,8 @@ impl_runtime_apis! {
         Block,
         AccountId,
         ArtvenusId,
+        BlockNumber,
+        Balance,
     > for Runtime {
         fn artvenuses() -> Vec<ArtvenusId> {
             Artvenuses::artvenuses()
@@ -478,10 +525,15 @@ impl_runtime_apis! {
         fn artvenuses_of_holder(account_id: AccountId) -> Vec<(u64, ArtvenusId)> {
             Artvenuses::artvenuses_of_holder(&account_id)
         }
+
+        fn provenance(id: ArtvenusId) -> Vec<ProvenanceRecord> {
+            Artvenuses::provenance(id)
+        }
     }
 
     impl cirml_market_runtime_api::MarketApi<
         Block,
+        AccountId,
         ArtvenusId,
         Balance,
         BlockNumber,
@@ -489,5 +541,21 @@ impl_runtime_apis! {
         fn on_sell() -> Vec<(ArtvenusId, OnSellInfo)> {
             Market::on_sell_list()
         }
+
+        fn quote_deal(venus_id: ArtvenusId) -> Option<DealQuote> {
+            Market::quote_deal(venus_id)
+        }
+
+        fn offers(venus_id: ArtvenusId) -> Vec<(AccountId, OfferInfo)> {
+            Market::offer_list(venus_id)
+        }
+
+        fn last_sale(venus_id: ArtvenusId) -> Option<SaleRecord> {
+            Market::last_sale(venus_id)
+        }
+
+        fn artist_sales(artist_id: ArtistId) -> SalesStats {
+            Market::artist_sales(artist_id)
+        }
     }
 }
@@ -1,6 +1,7 @@
 use cryptoindus_runtime::{
-    AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, MarketConfig, Signature,
-    SudoConfig, SystemConfig, WASM_BINARY,
+    AccountId, ArtvenusesConfig, AuraConfig, BalancesConfig, FeeSchedule, GenesisConfig,
+    GrandpaConfig, MarketConfig, Signature, StableBalancesConfig, SudoConfig, SystemConfig,
+    WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -132,10 +133,27 @@ fn testnet_genesis(
                 .map(|k| (k, 1 << 60))
                 .collect(),
         }),
+        cirml_balances_Instance1: Some(StableBalancesConfig {
+            balances: endowed_accounts
+                .iter()
+                .cloned()
+                .map(|k| (k, 1 << 60))
+                .collect(),
+        }),
+        cirml_artvenuses: Some(ArtvenusesConfig {}),
         cirml_market: Some(MarketConfig {
             manager: root_key,
-            virgin_sell_percent: Percent::from_percent(80),
-            normal_sell_percent: Percent::from_percent(2),
+            // the artist receives 80% in virgin sell
+            virgin_fee_schedule: FeeSchedule {
+                platform: Percent::from_percent(20),
+                royalty: Percent::from_percent(0),
+                beneficiaries: vec![],
+            },
+            normal_fee_schedule: FeeSchedule {
+                platform: Percent::from_percent(0),
+                royalty: Percent::from_percent(2),
+                beneficiaries: vec![],
+            },
         }),
     }
 }